@@ -0,0 +1,21 @@
+use std::time::Duration;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use grammar::{json::winnow_parse, json2::pest_parse};
+
+const FIXTURE: &str = include_str!("fixtures/medium.json");
+
+fn bench_json(c: &mut Criterion) {
+    // both backends must accept the fixture, otherwise the timings are meaningless
+    winnow_parse(FIXTURE).expect("winnow failed to parse the fixture");
+    pest_parse(FIXTURE).expect("pest failed to parse the fixture");
+
+    let mut group = c.benchmark_group("json");
+    group.warm_up_time(Duration::from_secs(2));
+    group.bench_function("winnow", |b| b.iter(|| winnow_parse(black_box(FIXTURE))));
+    group.bench_function("pest", |b| b.iter(|| pest_parse(black_box(FIXTURE))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_json);
+criterion_main!(benches);
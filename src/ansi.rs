@@ -0,0 +1,112 @@
+use winnow::{
+    combinator::{alt, eof, preceded, repeat, terminated},
+    token::{one_of, take_till, take_while},
+    PResult, Parser,
+};
+
+const ESC: char = '\x1b';
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnsiChunk {
+    Text(String),
+    /// Select Graphic Rendition parameters, e.g. `[1, 31]` for bold red.
+    Sgr(Vec<u16>),
+}
+
+/// Remove ANSI control sequences (`ESC [ ... final`) from `s`, keeping everything else.
+pub fn strip_ansi(s: &str) -> String {
+    let chunk = alt((parse_csi.value(""), take_till(1.., ESC), "\x1b"));
+    // every char matches one of the alternatives, so this can't fail
+    repeat(0.., chunk)
+        .fold(String::new, |mut acc, s| {
+            acc.push_str(s);
+            acc
+        })
+        .parse_next(&mut &*s)
+        .unwrap_or_default()
+}
+
+/// Split `s` into text and SGR sequences. Other control sequences, e.g. cursor movement,
+/// are dropped, while a malformed sequence is an error.
+// need to parse:
+// \x1b[1;31merror\x1b[0m: boom
+pub fn parse_ansi(s: &str) -> PResult<Vec<AnsiChunk>> {
+    let input = &mut (&*s);
+    let sequence = parse_csi.verify_map(|(params, action)| match action {
+        'm' => parse_sgr_params(params).map(|p| Some(AnsiChunk::Sgr(p))),
+        _ => Some(None),
+    });
+    let chunk = alt((
+        take_till(1.., ESC).map(|t: &str| Some(AnsiChunk::Text(t.to_string()))),
+        sequence,
+    ));
+    let chunks: Vec<Option<AnsiChunk>> = terminated(repeat(0.., chunk), eof).parse_next(input)?;
+    Ok(chunks.into_iter().flatten().collect())
+}
+
+// a CSI sequence as `(parameter bytes, final byte)`
+fn parse_csi<'a>(input: &mut &'a str) -> PResult<(&'a str, char)> {
+    preceded(
+        "\x1b[",
+        (
+            take_while(0.., '\x30'..='\x3f'),
+            preceded(take_while(0.., '\x20'..='\x2f'), one_of('\x40'..='\x7e')),
+        ),
+    )
+    .parse_next(input)
+}
+
+// empty parameters default to 0, so `ESC[m` is a reset
+fn parse_sgr_params(params: &str) -> Option<Vec<u16>> {
+    params
+        .split(';')
+        .map(|p| {
+            if p.is_empty() {
+                Some(0)
+            } else {
+                p.parse().ok()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_ansi_should_work() {
+        assert_eq!(
+            strip_ansi("\x1b[1;31merror\x1b[0m: disk \x1b[2Kfull"),
+            "error: disk full"
+        );
+        assert_eq!(strip_ansi("plain text"), "plain text");
+        // a lone escape isn't a control sequence
+        assert_eq!(strip_ansi("a\x1bb\x1b["), "a\x1bb\x1b[");
+    }
+
+    #[test]
+    fn parse_ansi_should_work() {
+        let ret = parse_ansi("\x1b[31mred\x1b[m done").unwrap();
+        assert_eq!(
+            ret,
+            vec![
+                AnsiChunk::Sgr(vec![31]),
+                AnsiChunk::Text("red".to_string()),
+                AnsiChunk::Sgr(vec![0]),
+                AnsiChunk::Text(" done".to_string()),
+            ]
+        );
+        let ret = parse_ansi("\x1b[1;;38;5;196m!\x1b[2K").unwrap();
+        assert_eq!(
+            ret,
+            vec![
+                AnsiChunk::Sgr(vec![1, 0, 38, 5, 196]),
+                AnsiChunk::Text("!".to_string()),
+            ]
+        );
+        assert!(parse_ansi("\x1b[31").is_err());
+        assert!(parse_ansi("\x1bx").is_err());
+        assert!(parse_ansi("\x1b[38:5:196m").is_err());
+    }
+}
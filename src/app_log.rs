@@ -0,0 +1,105 @@
+use chrono::NaiveDateTime;
+use winnow::{
+    ascii::{space0, space1},
+    combinator::{alt, delimited, rest, terminated},
+    token::{take, take_till},
+    PResult, Parser,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct AppLog {
+    pub timestamp: NaiveDateTime,
+    pub thread: String,
+    pub level: Level,
+    pub logger: String,
+    pub message: String,
+}
+
+// need to parse:
+// 2023-10-11 22:14:15.123 [main] INFO com.example.Foo - message text
+pub fn parse_app_log(s: &str) -> PResult<AppLog> {
+    let input = &mut (&*s);
+    let timestamp = terminated(parse_timestamp, space1).parse_next(input)?;
+    let thread = terminated(delimited('[', take_till(0.., ']'), ']'), space1).parse_next(input)?;
+    let level = terminated(parse_level, space1).parse_next(input)?;
+    let logger = terminated(take_till(1.., ' '), (space1, '-', space0)).parse_next(input)?;
+    let message = rest.parse_next(input)?;
+    Ok(AppLog {
+        timestamp,
+        thread: thread.to_string(),
+        level,
+        logger: logger.to_string(),
+        message: message.trim_end().to_string(),
+    })
+}
+
+fn parse_timestamp(s: &mut &str) -> PResult<NaiveDateTime> {
+    take(23usize)
+        .try_map(|ts| NaiveDateTime::parse_from_str(ts, "%Y-%m-%d %H:%M:%S%.3f"))
+        .parse_next(s)
+}
+
+fn parse_level(s: &mut &str) -> PResult<Level> {
+    alt((
+        "TRACE".value(Level::Trace),
+        "DEBUG".value(Level::Debug),
+        "INFO".value(Level::Info),
+        "WARN".value(Level::Warn),
+        "ERROR".value(Level::Error),
+    ))
+    .parse_next(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    #[test]
+    fn parse_app_log_should_work() {
+        let ret =
+            parse_app_log("2023-10-11 22:14:15.123 [main] INFO com.example.Foo - message text")
+                .unwrap();
+        assert_eq!(
+            ret,
+            AppLog {
+                timestamp: NaiveDate::from_ymd_opt(2023, 10, 11)
+                    .unwrap()
+                    .and_hms_milli_opt(22, 14, 15, 123)
+                    .unwrap(),
+                thread: "main".to_string(),
+                level: Level::Info,
+                logger: "com.example.Foo".to_string(),
+                message: "message text".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_app_log_with_brackets_in_message_should_work() {
+        let ret = parse_app_log(
+            "2023-10-11 22:14:15.123 [http-nio-8080-exec-1] ERROR c.e.Bar - failed [id=42] - retry [1/3]",
+        )
+        .unwrap();
+        assert_eq!(ret.thread, "http-nio-8080-exec-1");
+        assert_eq!(ret.level, Level::Error);
+        assert_eq!(ret.logger, "c.e.Bar");
+        assert_eq!(ret.message, "failed [id=42] - retry [1/3]");
+    }
+
+    #[test]
+    fn parse_invalid_app_log_should_fail() {
+        assert!(parse_app_log("2023-10-11 22:14:15.123 [main] FATAL Foo - boom").is_err());
+        assert!(parse_app_log("2023-13-11 22:14:15.123 [main] INFO Foo - boom").is_err());
+    }
+}
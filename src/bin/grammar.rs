@@ -0,0 +1,31 @@
+use std::{env, fs};
+
+use anyhow::{bail, Context, Result};
+use grammar::{json::winnow_parse, nginx_log::NginxLogStream};
+
+const USAGE: &str = "usage: grammar <json|nginx> <file>";
+
+fn main() -> Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let [command, file] = args.as_slice() else {
+        bail!(USAGE);
+    };
+    let content = fs::read_to_string(file).with_context(|| format!("Failed to read {}", file))?;
+
+    match command.as_str() {
+        "json" => {
+            let value = winnow_parse(&content).with_context(|| file.clone())?;
+            println!("{}", value.to_pretty_sorted(2));
+        }
+        "nginx" => {
+            // skips blank and `#` comment lines like `parse_nginx_logs`
+            for log in NginxLogStream::new(content.as_bytes()) {
+                let log = log.with_context(|| file.clone())?;
+                println!("{:?}", log);
+            }
+        }
+        _ => bail!(USAGE),
+    }
+
+    Ok(())
+}
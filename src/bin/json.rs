@@ -0,0 +1,21 @@
+use anyhow::Result;
+use grammar::json::winnow_parse;
+
+fn main() -> Result<()> {
+    let s = r#"{
+        "name": "John Doe",
+        "age": 43,
+        "is_student": false,
+        "marks": [87.0, 90, -45.7, 67.9],
+        "address": {
+            "city": "New York",
+            "zip": 10001
+        }
+    }"#;
+
+    let json = winnow_parse(s);
+
+    println!("{:#?}", json);
+
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use anyhow::Result;
+use grammar::json2::pest_parse;
+
+fn main() -> Result<()> {
+    let s = r#"{
+        "name": "John Doe",
+        "age": 43,
+        "is_student": false,
+        "marks": [87.0, 90, -45.7, 67.9],
+        "address": {
+            "city": "New York",
+            "zip": 10001
+        }
+    }"#;
+
+    let value = pest_parse(s)?;
+    println!("{:#?}", value);
+
+    Ok(())
+}
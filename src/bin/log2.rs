@@ -0,0 +1,9 @@
+use anyhow::{anyhow, Result};
+use grammar::nginx_log::parse_nginx_log;
+
+fn main() -> Result<()> {
+    let s = r#"93.180.71.3 - - [17/May/2015:08:05:32 +0000] "GET /downloads/product_1 HTTP/1.1" 304 0 "-" "Debian APT-HTTP/1.3 (0.8.16~exp12ubuntu10.21)""#;
+    let log = parse_nginx_log(s).map_err(|e| anyhow!("Failed to parse log: {:?}", e))?;
+    println!("{:?}", log);
+    Ok(())
+}
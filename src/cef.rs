@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+
+use winnow::{
+    ascii::digit1,
+    combinator::{
+        alt, delimited, dispatch, empty, eof, fail, not, preceded, repeat, separated,
+        separated_pair, terminated,
+    },
+    token::{any, none_of, one_of, take_while},
+    PResult, Parser,
+};
+
+/// An ArcSight Common Event Format record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CefEvent {
+    pub version: u32,
+    pub device_vendor: String,
+    pub device_product: String,
+    pub device_version: String,
+    pub signature_id: String,
+    pub name: String,
+    pub severity: String,
+    pub extension: HashMap<String, String>,
+}
+
+// need to parse:
+// CEF:0|Security|threatmanager|1.0|100|worm successfully stopped|10|src=10.0.0.1 dst=2.1.2.2 spt=1232
+pub fn parse_cef(s: &str) -> PResult<CefEvent> {
+    let input = &mut s.trim();
+    let version = delimited("CEF:", digit1.parse_to(), '|').parse_next(input)?;
+    let mut header: Vec<String> =
+        repeat(6, terminated(parse_header_field, '|')).parse_next(input)?;
+    let extension = terminated(parse_extension, eof).parse_next(input)?;
+
+    let mut next = || header.remove(0);
+    Ok(CefEvent {
+        version,
+        device_vendor: next(),
+        device_product: next(),
+        device_version: next(),
+        signature_id: next(),
+        name: next(),
+        severity: next(),
+        extension,
+    })
+}
+
+// `|` and `\` are escaped with a backslash inside header fields
+fn parse_header_field(s: &mut &str) -> PResult<String> {
+    repeat(
+        0..,
+        alt((preceded('\\', one_of(['|', '\\'])), none_of(['|', '\\']))),
+    )
+    .parse_next(s)
+}
+
+fn parse_extension(s: &mut &str) -> PResult<HashMap<String, String>> {
+    let pair = separated_pair(parse_key.map(str::to_string), '=', parse_value);
+    alt((separated(1.., pair, ' '), empty.value(HashMap::new()))).parse_next(s)
+}
+
+fn parse_key<'a>(s: &mut &'a str) -> PResult<&'a str> {
+    take_while(1.., |c: char| {
+        c.is_ascii_alphanumeric() || "_.[]".contains(c)
+    })
+    .parse_next(s)
+}
+
+// values may contain spaces and unescaped `=`, they run until the next ` key=`
+fn parse_value(s: &mut &str) -> PResult<String> {
+    let escaped = dispatch! {any;
+        '=' => empty.value('='),
+        '\\' => empty.value('\\'),
+        'n' => empty.value('\n'),
+        'r' => empty.value('\r'),
+        _ => fail,
+    };
+    repeat(
+        0..,
+        alt((
+            preceded('\\', escaped),
+            terminated(' ', not((parse_key, '='))),
+            none_of([' ', '\\']),
+        )),
+    )
+    .parse_next(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_cef_should_work() {
+        let ret = parse_cef(
+            "CEF:0|Security|threatmanager|1.0|100|worm successfully stopped|10|src=10.0.0.1 dst=2.1.2.2 msg=Detected a threat. No action needed url=http://x/?a=b",
+        )
+        .unwrap();
+        assert_eq!(ret.version, 0);
+        assert_eq!(ret.device_vendor, "Security");
+        assert_eq!(ret.device_product, "threatmanager");
+        assert_eq!(ret.device_version, "1.0");
+        assert_eq!(ret.signature_id, "100");
+        assert_eq!(ret.name, "worm successfully stopped");
+        assert_eq!(ret.severity, "10");
+
+        let extension: HashMap<String, String> = [
+            ("src", "10.0.0.1"),
+            ("dst", "2.1.2.2"),
+            ("msg", "Detected a threat. No action needed"),
+            ("url", "http://x/?a=b"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        assert_eq!(ret.extension, extension);
+    }
+
+    #[test]
+    fn parse_cef_with_escapes_should_work() {
+        let ret =
+            parse_cef(r"CEF:0|security|threatmanager|1.0|100|detected a \| in message|10|act=blocked a \= b\\c")
+                .unwrap();
+        assert_eq!(ret.name, "detected a | in message");
+        assert_eq!(ret.severity, "10");
+        assert_eq!(ret.extension["act"], r"blocked a = b\c");
+
+        let ret = parse_cef("CEF:1|a|b|c|d|e|Low|").unwrap();
+        assert_eq!(ret.version, 1);
+        assert!(ret.extension.is_empty());
+
+        assert!(parse_cef("CEF:0|a|b|c|d|e").is_err());
+    }
+}
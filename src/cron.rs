@@ -0,0 +1,113 @@
+use std::collections::BTreeSet;
+
+use winnow::{
+    ascii::{dec_uint, space0, space1},
+    combinator::{alt, eof, opt, preceded, separated},
+    PResult, Parser,
+};
+
+/// A crontab schedule, each field holding the set of values it allows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    pub minute: BTreeSet<u8>,
+    pub hour: BTreeSet<u8>,
+    pub day_of_month: BTreeSet<u8>,
+    pub month: BTreeSet<u8>,
+    pub day_of_week: BTreeSet<u8>,
+}
+
+// need to parse:
+// */15 0 * * 1-5
+pub fn parse_cron(s: &str) -> PResult<CronSchedule> {
+    let input = &mut (&*s);
+    space0(input)?;
+    let minute = parse_field(input, 0, 59)?;
+    space1(input)?;
+    let hour = parse_field(input, 0, 23)?;
+    space1(input)?;
+    let day_of_month = parse_field(input, 1, 31)?;
+    space1(input)?;
+    let month = parse_field(input, 1, 12)?;
+    space1(input)?;
+    let day_of_week = parse_field(input, 0, 6)?;
+    (space0, eof).parse_next(input)?;
+    Ok(CronSchedule {
+        minute,
+        hour,
+        day_of_month,
+        month,
+        day_of_week,
+    })
+}
+
+fn parse_field(s: &mut &str, min: u8, max: u8) -> PResult<BTreeSet<u8>> {
+    let items: Vec<Vec<u8>> =
+        separated(1.., |i: &mut &str| parse_item(i, min, max), ',').parse_next(s)?;
+    Ok(items.into_iter().flatten().collect())
+}
+
+// an item is `*`, `a` or `a-b`, optionally followed by a `/n` step
+fn parse_item(s: &mut &str, min: u8, max: u8) -> PResult<Vec<u8>> {
+    let (start, end) = alt((
+        '*'.value((min, Some(max))),
+        (
+            |i: &mut &str| parse_number(i, min, max),
+            opt(preceded('-', |i: &mut &str| parse_number(i, min, max))),
+        ),
+    ))
+    .verify(|(start, end)| end.is_none_or(|end| *start <= end))
+    .parse_next(s)?;
+    let step = opt(preceded('/', dec_uint::<_, u8, _>.verify(|step| *step > 0))).parse_next(s)?;
+    let ret = match (end, step) {
+        (Some(end), step) => (start..=end).step_by(step.unwrap_or(1) as usize).collect(),
+        // `a/n` means every n-th value starting from a
+        (None, Some(step)) => (start..=max).step_by(step as usize).collect(),
+        (None, None) => vec![start],
+    };
+    Ok(ret)
+}
+
+fn parse_number(s: &mut &str, min: u8, max: u8) -> PResult<u8> {
+    dec_uint.verify(|v| (min..=max).contains(v)).parse_next(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_cron_wildcards_should_work() {
+        let ret = parse_cron("* * * * *").unwrap();
+        assert_eq!(ret.minute, (0..=59).collect());
+        assert_eq!(ret.hour, (0..=23).collect());
+        assert_eq!(ret.day_of_month, (1..=31).collect());
+        assert_eq!(ret.month, (1..=12).collect());
+        assert_eq!(ret.day_of_week, (0..=6).collect());
+    }
+
+    #[test]
+    fn parse_cron_step_should_work() {
+        let ret = parse_cron("*/15 0 * * 1-5").unwrap();
+        assert_eq!(ret.minute, [0, 15, 30, 45].into());
+        assert_eq!(ret.hour, [0].into());
+        assert_eq!(ret.day_of_week, (1..=5).collect());
+    }
+
+    #[test]
+    fn parse_cron_range_with_list_should_work() {
+        let ret = parse_cron("0,30 9-17/4,22 1,15 6-8 *").unwrap();
+        assert_eq!(ret.minute, [0, 30].into());
+        assert_eq!(ret.hour, [9, 13, 17, 22].into());
+        assert_eq!(ret.day_of_month, [1, 15].into());
+        assert_eq!(ret.month, [6, 7, 8].into());
+    }
+
+    #[test]
+    fn parse_cron_out_of_range_should_fail() {
+        assert!(parse_cron("60 * * * *").is_err());
+        assert!(parse_cron("* 24 * * *").is_err());
+        assert!(parse_cron("* * 0 * *").is_err());
+        assert!(parse_cron("* * * * 5-1").is_err());
+        assert!(parse_cron("* * * *").is_err());
+    }
+}
@@ -0,0 +1,89 @@
+use winnow::{
+    ascii::{digit1, space1},
+    combinator::{delimited, eof, opt, preceded, rest, separated_pair},
+    PResult, Parser,
+};
+
+/// The `@@ -l,s +l,s @@` line starting a hunk of a unified diff. A count is `None` when
+/// the header omits it, which means the range is a single line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HunkHeader {
+    pub old_start: u32,
+    pub old_count: Option<u32>,
+    pub new_start: u32,
+    pub new_count: Option<u32>,
+    pub section: Option<String>,
+}
+
+// need to parse:
+// @@ -1,5 +2,6 @@ fn main() {
+pub fn parse_hunk_header(s: &str) -> PResult<HunkHeader> {
+    let input = &mut s.trim_end_matches(['\r', '\n']);
+    let ((old_start, old_count), (new_start, new_count)) = delimited(
+        "@@ ",
+        separated_pair(preceded('-', parse_range), ' ', preceded('+', parse_range)),
+        " @@",
+    )
+    .parse_next(input)?;
+    // anything after the closing `@@` is the enclosing function or section
+    let section = opt(preceded(space1, rest)).parse_next(input)?;
+    eof.parse_next(input)?;
+    Ok(HunkHeader {
+        old_start,
+        old_count,
+        new_start,
+        new_count,
+        section: section.filter(|s| !s.is_empty()).map(str::to_string),
+    })
+}
+
+fn parse_range(s: &mut &str) -> PResult<(u32, Option<u32>)> {
+    (digit1.parse_to(), opt(preceded(',', digit1.parse_to()))).parse_next(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_hunk_header_should_work() {
+        assert_eq!(
+            parse_hunk_header("@@ -1,5 +2,6 @@").unwrap(),
+            HunkHeader {
+                old_start: 1,
+                old_count: Some(5),
+                new_start: 2,
+                new_count: Some(6),
+                section: None,
+            }
+        );
+        assert!(parse_hunk_header("@@ -1,5 @@").is_err());
+        assert!(parse_hunk_header("@@ -a,5 +2,6 @@").is_err());
+    }
+
+    #[test]
+    fn parse_hunk_header_with_omitted_counts_should_work() {
+        assert_eq!(
+            parse_hunk_header("@@ -3 +3,0 @@\n").unwrap(),
+            HunkHeader {
+                old_start: 3,
+                old_count: None,
+                new_start: 3,
+                new_count: Some(0),
+                section: None,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_hunk_header_with_section_should_work() {
+        let ret =
+            parse_hunk_header("@@ -10,7 +10,8 @@ fn parse_value(input: &mut &str) {").unwrap();
+        assert_eq!(ret.old_start, 10);
+        assert_eq!(ret.new_count, Some(8));
+        assert_eq!(
+            ret.section.as_deref(),
+            Some("fn parse_value(input: &mut &str) {")
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use std::net::Ipv6Addr;
+
+use winnow::{
+    ascii::{alphanumeric1, digit1, space1},
+    combinator::{alt, eof, opt, rest, terminated},
+    error::{ErrMode, ErrorKind, ParserError},
+    token::take_till,
+    PResult, Parser,
+};
+
+use crate::nginx_log::parse_ip;
+
+/// A resource record line of a BIND zone file. `ttl` and `class` are `None` when omitted,
+/// in which case they are inherited from the zone defaults.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceRecord {
+    pub name: String,
+    pub ttl: Option<u32>,
+    pub class: Option<String>,
+    pub rtype: String,
+    pub rdata: String,
+}
+
+// need to parse:
+// example.com. 3600 IN A 1.2.3.4
+pub fn parse_rr(s: &str) -> PResult<ResourceRecord> {
+    let input = &mut s.trim();
+    let name = terminated(take_till(1.., [' ', '\t']), space1).parse_next(input)?;
+    // TTL and class may come in either order
+    let mut ttl = opt(parse_ttl).parse_next(input)?;
+    let class = opt(parse_class).parse_next(input)?;
+    if ttl.is_none() {
+        ttl = opt(parse_ttl).parse_next(input)?;
+    }
+    let rtype = terminated(alphanumeric1, space1).parse_next(input)?;
+    let rdata = rest.map(str::trim).parse_next(input)?;
+    let valid = match rtype {
+        "A" => (parse_ip, eof).parse_next(&mut &*rdata).is_ok(),
+        "AAAA" => rdata.parse::<Ipv6Addr>().is_ok(),
+        _ => !rdata.is_empty(),
+    };
+    if !valid {
+        return Err(ErrMode::from_error_kind(input, ErrorKind::Verify));
+    }
+    Ok(ResourceRecord {
+        name: name.to_string(),
+        ttl,
+        class: class.map(str::to_string),
+        rtype: rtype.to_string(),
+        rdata: rdata.to_string(),
+    })
+}
+
+fn parse_ttl(s: &mut &str) -> PResult<u32> {
+    terminated(digit1.parse_to(), space1).parse_next(s)
+}
+
+fn parse_class<'a>(s: &mut &'a str) -> PResult<&'a str> {
+    terminated(alt(("IN", "CH", "HS", "CS")), space1).parse_next(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_a_record_should_work() {
+        let ret = parse_rr("example.com. 3600 IN A 1.2.3.4").unwrap();
+        assert_eq!(
+            ret,
+            ResourceRecord {
+                name: "example.com.".to_string(),
+                ttl: Some(3600),
+                class: Some("IN".to_string()),
+                rtype: "A".to_string(),
+                rdata: "1.2.3.4".to_string(),
+            }
+        );
+        assert!(parse_rr("example.com. 3600 IN A 1.2.3").is_err());
+        assert!(parse_rr("example.com. 3600 IN AAAA 1.2.3.4").is_err());
+        assert!(parse_rr("example.com. IN AAAA 2001:db8::1").is_ok());
+    }
+
+    #[test]
+    fn parse_cname_record_should_work() {
+        let ret = parse_rr("www.example.com.\t300\tIN\tCNAME\texample.com.").unwrap();
+        assert_eq!(
+            ret,
+            ResourceRecord {
+                name: "www.example.com.".to_string(),
+                ttl: Some(300),
+                class: Some("IN".to_string()),
+                rtype: "CNAME".to_string(),
+                rdata: "example.com.".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_record_without_ttl_should_work() {
+        let ret = parse_rr("mail IN MX 10 mx.example.com.").unwrap();
+        assert_eq!(ret.ttl, None);
+        assert_eq!(ret.class.as_deref(), Some("IN"));
+        assert_eq!(ret.rtype, "MX");
+        assert_eq!(ret.rdata, "10 mx.example.com.");
+
+        let ret = parse_rr("@ TXT \"v=spf1 -all\"").unwrap();
+        assert_eq!(ret.ttl, None);
+        assert_eq!(ret.class, None);
+        assert_eq!(ret.rdata, "\"v=spf1 -all\"");
+
+        let ret = parse_rr("ftp IN 60 A 10.0.0.1").unwrap();
+        assert_eq!(ret.ttl, Some(60));
+    }
+}
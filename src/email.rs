@@ -0,0 +1,141 @@
+use anyhow::{anyhow, Result};
+use winnow::{
+    combinator::{alt, delimited, eof, opt, preceded, repeat, separated, terminated},
+    stream::AsChar,
+    token::{any, none_of, take_till, take_while},
+    PResult, Parser,
+};
+
+/// Parse an email address into its local part and domain.
+///
+/// This covers the practical subset of RFC 5322: dot-atom or quoted local parts and
+/// dotted domain labels. Quoted local parts are returned verbatim, including the quotes.
+pub fn parse_email(s: &str) -> PResult<(String, String)> {
+    let input = &mut (&*s);
+    let (local, _, domain) =
+        terminated((parse_local_part, '@', parse_domain), eof).parse_next(input)?;
+    Ok((local.to_string(), domain.to_string()))
+}
+
+/// Decode quoted-printable text (RFC 2045), the decoded bytes must be valid UTF-8.
+pub fn decode_quoted_printable(s: &str) -> Result<String> {
+    let input = &mut (&*s);
+    let bytes = terminated(parse_quoted_printable, eof)
+        .parse_next(input)
+        .map_err(|e| anyhow!("Invalid quoted-printable text: {:?}", e))?;
+    Ok(String::from_utf8(bytes)?)
+}
+
+fn parse_quoted_printable(s: &mut &str) -> PResult<Vec<u8>> {
+    let chunk = alt((
+        // a soft line break joins two lines
+        ('=', opt('\r'), '\n').value(vec![]),
+        preceded('=', take_while(2, AsChar::is_hex_digit))
+            .try_map(|hex| u8::from_str_radix(hex, 16))
+            .map(|b| vec![b]),
+        take_till(1.., '=').map(|s: &str| s.as_bytes().to_vec()),
+    ));
+    repeat(0.., chunk)
+        .fold(Vec::new, |mut acc, chunk: Vec<u8>| {
+            acc.extend(chunk);
+            acc
+        })
+        .parse_next(s)
+}
+
+fn parse_local_part<'a>(s: &mut &'a str) -> PResult<&'a str> {
+    alt((parse_dot_atom, parse_quoted_local)).parse_next(s)
+}
+
+fn parse_dot_atom<'a>(s: &mut &'a str) -> PResult<&'a str> {
+    separated(1.., take_while(1.., is_atext), '.')
+        .map(|()| ())
+        .take()
+        .parse_next(s)
+}
+
+fn parse_quoted_local<'a>(s: &mut &'a str) -> PResult<&'a str> {
+    let quoted_char = alt((preceded('\\', any).void(), none_of(['"', '\\']).void()));
+    delimited('"', repeat(0.., quoted_char).map(|()| ()), '"')
+        .take()
+        .parse_next(s)
+}
+
+fn parse_domain<'a>(s: &mut &'a str) -> PResult<&'a str> {
+    separated(1.., parse_label, '.')
+        .map(|()| ())
+        .take()
+        .parse_next(s)
+}
+
+// a domain label can't start or end with a hyphen
+fn parse_label<'a>(s: &mut &'a str) -> PResult<&'a str> {
+    take_while(1.., |c: char| c.is_ascii_alphanumeric() || c == '-')
+        .verify(|label: &str| !label.starts_with('-') && !label.ends_with('-'))
+        .parse_next(s)
+}
+
+fn is_atext(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-/=?^_`{|}~".contains(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_email_should_work() {
+        let (local, domain) = parse_email("john@example.com").unwrap();
+        assert_eq!(local, "john");
+        assert_eq!(domain, "example.com");
+    }
+
+    #[test]
+    fn parse_email_with_tag_should_work() {
+        let (local, domain) = parse_email("user.name+tag@sub.example.com").unwrap();
+        assert_eq!(local, "user.name+tag");
+        assert_eq!(domain, "sub.example.com");
+    }
+
+    #[test]
+    fn parse_email_with_quoted_local_part_should_work() {
+        let (local, domain) = parse_email(r#""john \"jd\" doe"@example.com"#).unwrap();
+        assert_eq!(local, r#""john \"jd\" doe""#);
+        assert_eq!(domain, "example.com");
+    }
+
+    #[test]
+    fn parse_invalid_email_should_fail() {
+        assert!(parse_email("john.example.com").is_err());
+        assert!(parse_email("john@").is_err());
+        assert!(parse_email("john..doe@example.com").is_err());
+        assert!(parse_email("john@-example.com").is_err());
+    }
+
+    #[test]
+    fn decode_quoted_printable_should_work() -> Result<()> {
+        assert_eq!(decode_quoted_printable("hello=20world")?, "hello world");
+        assert_eq!(decode_quoted_printable("caf=C3=A9 =3D 1")?, "café = 1");
+        assert_eq!(decode_quoted_printable("")?, "");
+
+        Ok(())
+    }
+
+    #[test]
+    fn decode_quoted_printable_soft_break_should_work() -> Result<()> {
+        assert_eq!(
+            decode_quoted_printable("a long line that was wra=\r\npped\nnext")?,
+            "a long line that was wrapped\nnext"
+        );
+        assert_eq!(decode_quoted_printable("soft=\nbreak")?, "softbreak");
+
+        Ok(())
+    }
+
+    #[test]
+    fn decode_invalid_quoted_printable_should_fail() {
+        assert!(decode_quoted_printable("bad =G1 escape").is_err());
+        assert!(decode_quoted_printable("truncated =4").is_err());
+        assert!(decode_quoted_printable("=FF=FE").is_err());
+    }
+}
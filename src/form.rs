@@ -0,0 +1,292 @@
+use anyhow::{anyhow, Result};
+use serde::{
+    de::{
+        self,
+        value::{Error, MapDeserializer, SeqDeserializer},
+        DeserializeOwned, IntoDeserializer, Visitor,
+    },
+    forward_to_deserialize_any, Deserializer,
+};
+use winnow::{
+    combinator::{eof, opt, preceded, separated, terminated},
+    error::{ErrMode, ErrorKind, ParserError},
+    stream::AsChar,
+    token::{take_till, take_while},
+    PResult, Parser,
+};
+
+/// Parse an `application/x-www-form-urlencoded` string (or URL query) into decoded pairs.
+///
+/// A leading `?` is ignored, keys without `=` get an empty value, and `+` decodes to a space.
+pub fn parse_query(s: &str) -> PResult<Vec<(String, String)>> {
+    let input = &mut (&*s);
+    opt('?').parse_next(input)?;
+    let pair = (
+        parse_form_component,
+        opt(preceded('=', parse_form_component)),
+    );
+    let pairs: Vec<_> = terminated(separated(0.., pair, '&'), eof).parse_next(input)?;
+    Ok(pairs
+        .into_iter()
+        .filter(|(k, _)| !k.is_empty())
+        .map(|(k, v)| (k, v.unwrap_or_default()))
+        .collect())
+}
+
+/// Decode `%XX` escapes, the decoded bytes must be valid UTF-8.
+pub fn percent_decode(s: &str) -> PResult<String> {
+    let input = &mut (&*s);
+    let mut bytes = Vec::with_capacity(s.len());
+    while !input.is_empty() {
+        match opt(parse_encoded_byte).parse_next(input)? {
+            Some(b) => bytes.push(b),
+            None => bytes.extend(take_till(1.., '%').parse_next(input)?.as_bytes()),
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| ErrMode::from_error_kind(input, ErrorKind::Verify))
+}
+
+/// Deserialize an urlencoded body into `T`, repeated keys map to sequence fields.
+pub fn from_form<T: DeserializeOwned>(body: &str) -> Result<T> {
+    let pairs = parse_query(body).map_err(|e| anyhow!("Failed to parse form: {:?}", e))?;
+    let mut grouped: Vec<(String, FormValue)> = Vec::new();
+    for (k, v) in pairs {
+        match grouped.iter_mut().find(|(key, _)| *key == k) {
+            Some((_, values)) => values.0.push(v),
+            None => grouped.push((k, FormValue(vec![v]))),
+        }
+    }
+    let deserializer = MapDeserializer::<_, Error>::new(grouped.into_iter());
+    Ok(T::deserialize(deserializer)?)
+}
+
+fn parse_form_component(s: &mut &str) -> PResult<String> {
+    let raw = take_till(0.., ['&', '=']).parse_next(s)?;
+    percent_decode(&raw.replace('+', " "))
+}
+
+fn parse_encoded_byte(s: &mut &str) -> PResult<u8> {
+    preceded('%', take_while(2, AsChar::is_hex_digit))
+        .try_map(|hex| u8::from_str_radix(hex, 16))
+        .parse_next(s)
+}
+
+// all values seen for a key, a single value deserializes as a scalar and several as a sequence
+struct FormValue(Vec<String>);
+
+// a single form value, parsed on demand into whatever type the visitor asks for
+struct Part(String);
+
+impl FormValue {
+    fn into_last(self) -> Part {
+        Part(self.0.into_iter().last().unwrap_or_default())
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for FormValue {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Part {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+macro_rules! forward_to_last_part {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                self.into_last().$method(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for FormValue {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.0.len() > 1 {
+            self.deserialize_seq(visitor)
+        } else {
+            self.into_last().deserialize_any(visitor)
+        }
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let mut seq = SeqDeserializer::new(self.0.into_iter().map(Part));
+        let ret = visitor.visit_seq(&mut seq)?;
+        seq.end()?;
+        Ok(ret)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.into_last().deserialize_enum(name, variants, visitor)
+    }
+
+    forward_to_last_part! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64 deserialize_f32
+        deserialize_f64 deserialize_char deserialize_str deserialize_string
+    }
+
+    forward_to_deserialize_any! {
+        bytes byte_buf unit unit_struct tuple_struct map struct identifier ignored_any
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                visitor.$visit(self.0.parse().map_err(de::Error::custom)?)
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for Part {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_string(self.0)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        IntoDeserializer::<Error>::into_deserializer(self.0)
+            .deserialize_enum(name, variants, visitor)
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool
+        deserialize_i8 => visit_i8
+        deserialize_i16 => visit_i16
+        deserialize_i32 => visit_i32
+        deserialize_i64 => visit_i64
+        deserialize_u8 => visit_u8
+        deserialize_u16 => visit_u16
+        deserialize_u32 => visit_u32
+        deserialize_u64 => visit_u64
+        deserialize_f32 => visit_f32
+        deserialize_f64 => visit_f64
+        deserialize_char => visit_char
+    }
+
+    forward_to_deserialize_any! {
+        str string bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Form {
+        name: String,
+        age: u32,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Search {
+        q: String,
+        tag: Vec<String>,
+        page: Option<u32>,
+    }
+
+    #[test]
+    fn parse_query_should_work() {
+        let ret = parse_query("?name=John+Doe&city=New%20York&flag").unwrap();
+        assert_eq!(
+            ret,
+            vec![
+                ("name".to_string(), "John Doe".to_string()),
+                ("city".to_string(), "New York".to_string()),
+                ("flag".to_string(), "".to_string()),
+            ]
+        );
+        assert!(parse_query("name=%zz").is_err());
+    }
+
+    #[test]
+    fn from_form_should_work() -> Result<()> {
+        let form: Form = from_form("name=Bob&age=30")?;
+        assert_eq!(
+            form,
+            Form {
+                name: "Bob".to_string(),
+                age: 30
+            }
+        );
+        assert!(from_form::<Form>("name=Bob&age=thirty").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn from_form_with_repeated_keys_should_work() -> Result<()> {
+        let search: Search = from_form("q=rust%2Bwinnow&tag=parser&tag=json")?;
+        assert_eq!(
+            search,
+            Search {
+                q: "rust+winnow".to_string(),
+                tag: vec!["parser".to_string(), "json".to_string()],
+                page: None,
+            }
+        );
+
+        Ok(())
+    }
+}
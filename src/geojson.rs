@@ -0,0 +1,121 @@
+use anyhow::{anyhow, bail, Result};
+
+use crate::json::{JsonValue, Num};
+
+/// A typed GeoJSON geometry.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Geometry {
+    Point([f64; 2]),
+    LineString(Vec<[f64; 2]>),
+    Polygon(Vec<Vec<[f64; 2]>>),
+}
+
+/// Extract a typed geometry from a parsed GeoJSON geometry object by reading its
+/// `type` and `coordinates` members.
+pub fn parse_geometry(value: &JsonValue) -> Result<Geometry> {
+    let JsonValue::Object(obj) = value else {
+        bail!("geometry must be an object");
+    };
+    let kind = match obj.get("type") {
+        Some(JsonValue::String(kind)) => kind.as_str(),
+        _ => bail!("geometry has no string `type` member"),
+    };
+    let coordinates = obj
+        .get("coordinates")
+        .ok_or_else(|| anyhow!("geometry has no `coordinates` member"))?;
+
+    let ret = match kind {
+        "Point" => Geometry::Point(
+            parse_position(coordinates)
+                .map_err(|e| anyhow!("invalid coordinates for Point: {}", e))?,
+        ),
+        "LineString" => Geometry::LineString(
+            parse_positions(coordinates)
+                .map_err(|e| anyhow!("invalid coordinates for LineString: {}", e))?,
+        ),
+        "Polygon" => Geometry::Polygon(
+            as_array(coordinates)
+                .and_then(|rings| rings.iter().map(parse_positions).collect())
+                .map_err(|e| anyhow!("invalid coordinates for Polygon: {}", e))?,
+        ),
+        v => bail!("unsupported geometry type: {}", v),
+    };
+
+    Ok(ret)
+}
+
+fn parse_positions(value: &JsonValue) -> Result<Vec<[f64; 2]>> {
+    as_array(value)?.iter().map(parse_position).collect()
+}
+
+// a position is `[x, y]`, optionally followed by an altitude which we ignore
+fn parse_position(value: &JsonValue) -> Result<[f64; 2]> {
+    match as_array(value)? {
+        [x, y, ..] => Ok([as_f64(x)?, as_f64(y)?]),
+        _ => bail!("expected a position with at least 2 numbers"),
+    }
+}
+
+fn as_array(value: &JsonValue) -> Result<&[JsonValue]> {
+    match value {
+        JsonValue::Array(arr) => Ok(arr),
+        _ => bail!("expected an array, got {:?}", value),
+    }
+}
+
+fn as_f64(value: &JsonValue) -> Result<f64> {
+    match value {
+        JsonValue::Number(Num::Int(v)) => Ok(*v as f64),
+        JsonValue::Number(Num::Float(v)) => Ok(*v),
+        _ => bail!("expected a number, got {:?}", value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::winnow_parse;
+
+    #[test]
+    fn parse_point_should_work() -> Result<()> {
+        let value = winnow_parse(r#"{"type": "Point", "coordinates": [102.0, 0.5]}"#)?;
+        assert_eq!(parse_geometry(&value)?, Geometry::Point([102.0, 0.5]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_line_string_should_work() -> Result<()> {
+        let value = winnow_parse(
+            r#"{"type": "LineString", "coordinates": [[102.0, 0.0], [103, 1.0], [104.0, 0.0]]}"#,
+        )?;
+        assert_eq!(
+            parse_geometry(&value)?,
+            Geometry::LineString(vec![[102.0, 0.0], [103.0, 1.0], [104.0, 0.0]])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_polygon_should_work() -> Result<()> {
+        let value = winnow_parse(
+            r#"{"type": "Polygon", "coordinates": [[[100.0, 0.0], [101.0, 0.0], [100.0, 0.0]]]}"#,
+        )?;
+        assert_eq!(
+            parse_geometry(&value)?,
+            Geometry::Polygon(vec![vec![[100.0, 0.0], [101.0, 0.0], [100.0, 0.0]]])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_mismatched_coordinates_should_fail() -> Result<()> {
+        let value = winnow_parse(r#"{"type": "Point", "coordinates": [[102.0, 0.5]]}"#)?;
+        let err = parse_geometry(&value).unwrap_err();
+        assert!(err.to_string().starts_with("invalid coordinates for Point"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+use winnow::{
+    combinator::{alt, delimited, eof, opt, preceded, repeat, terminated},
+    token::{any, none_of},
+    PResult, Parser,
+};
+
+/// A compiled glob pattern matched against `/`-separated paths.
+///
+/// `*` and `?` never match `/`, a `**` path segment matches across directories and `[a-z]` /
+/// `[!a-z]` are character classes. A backslash escapes the next character.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Glob {
+    tokens: Vec<Token>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Literal(char),
+    AnyChar,
+    Star,
+    DoubleStar,
+    Class {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+}
+
+pub fn parse_glob(s: &str) -> PResult<Glob> {
+    let input = &mut (&*s);
+    let token = alt((
+        "**".value(Token::DoubleStar),
+        '*'.value(Token::Star),
+        '?'.value(Token::AnyChar),
+        parse_class,
+        preceded('\\', any).map(Token::Literal),
+        any.map(Token::Literal),
+    ));
+    let mut tokens: Vec<Token> = terminated(repeat(0.., token), eof).parse_next(input)?;
+    // `**` only crosses directories as a whole segment, elsewhere it's a plain `*`
+    for i in 0..tokens.len() {
+        let starts_segment = i == 0 || tokens[i - 1] == Token::Literal('/');
+        let ends_segment = tokens.get(i + 1).is_none_or(|t| *t == Token::Literal('/'));
+        if tokens[i] == Token::DoubleStar && !(starts_segment && ends_segment) {
+            tokens[i] = Token::Star;
+        }
+    }
+    Ok(Glob { tokens })
+}
+
+fn parse_class(s: &mut &str) -> PResult<Token> {
+    let range = (none_of(']'), opt(preceded('-', none_of(']'))))
+        .map(|(start, end)| (start, end.unwrap_or(start)));
+    let negated = opt(alt(('!', '^'))).map(|n| n.is_some());
+    delimited('[', (negated, repeat(1.., range)), ']')
+        .map(|(negated, ranges)| Token::Class { negated, ranges })
+        .parse_next(s)
+}
+
+impl Glob {
+    pub fn matches(&self, path: &str) -> bool {
+        let text: Vec<char> = path.chars().collect();
+        match_tokens(&self.tokens, &text)
+    }
+}
+
+// `matched[t][i]` tells whether `tokens[t..]` matches `text[i..]`, filled in from the end
+// so stars don't backtrack exponentially
+fn match_tokens(tokens: &[Token], text: &[char]) -> bool {
+    let mut matched = vec![vec![false; text.len() + 1]; tokens.len() + 1];
+    matched[tokens.len()][text.len()] = true;
+    for (t, token) in tokens.iter().enumerate().rev() {
+        for i in (0..=text.len()).rev() {
+            let c = text.get(i);
+            matched[t][i] = match token {
+                Token::Star => {
+                    matched[t + 1][i] || (c.is_some_and(|c| *c != '/') && matched[t][i + 1])
+                }
+                Token::DoubleStar => {
+                    matched[t + 1][i]
+                        || (c.is_some() && matched[t][i + 1])
+                        // `**/` also matches zero directories, so `a/**/b` matches `a/b`
+                        || (tokens.get(t + 1) == Some(&Token::Literal('/')) && matched[t + 2][i])
+                }
+                _ => c.is_some_and(|c| match_char(token, *c)) && matched[t + 1][i + 1],
+            };
+        }
+    }
+    matched[0][0]
+}
+
+fn match_char(token: &Token, c: char) -> bool {
+    match token {
+        Token::Literal(l) => *l == c,
+        Token::AnyChar => c != '/',
+        Token::Class { negated, ranges } => {
+            c != '/'
+                && ranges
+                    .iter()
+                    .any(|(start, end)| (*start..=*end).contains(&c))
+                    != *negated
+        }
+        Token::Star | Token::DoubleStar => unreachable!("handled by match_tokens"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, path: &str) -> bool {
+        parse_glob(pattern).unwrap().matches(path)
+    }
+
+    #[test]
+    fn glob_should_work() {
+        assert!(matches("*.rs", "main.rs"));
+        assert!(!matches("*.rs", "src/main.rs"));
+        assert!(matches("src/?ain.rs", "src/main.rs"));
+        assert!(matches("file[0-9].txt", "file7.txt"));
+        assert!(!matches("file[!0-9].txt", "file7.txt"));
+        assert!(matches(r"\*.txt", "*.txt"));
+        assert!(!matches(r"\*.txt", "a.txt"));
+    }
+
+    #[test]
+    fn glob_double_star_should_work() {
+        assert!(matches("**/*.rs", "src/json/ser.rs"));
+        assert!(matches("**/*.rs", "lib.rs"));
+        assert!(matches("src/**/mod.rs", "src/mod.rs"));
+        assert!(matches("src/**", "src/a/b/c"));
+        assert!(!matches("src/**/mod.rs", "tests/mod.rs"));
+    }
+
+    #[test]
+    fn glob_double_star_inside_segment_should_be_a_star() {
+        assert!(matches("a**b", "axxb"));
+        assert!(!matches("a**b", "a/b"));
+        assert!(!matches("src**", "src/a"));
+        assert!(!matches("**.rs", "src/main.rs"));
+        assert!(matches("**.rs", "main.rs"));
+    }
+
+    #[test]
+    fn glob_with_many_stars_should_not_backtrack() {
+        let path = "a".repeat(64);
+        assert!(!matches("*a*a*a*a*a*a*a*a*a*a*a*a*b", &path));
+        assert!(!matches(
+            "**/**/**/**/**/**/**/**/b",
+            &format!("{}/c", path)
+        ));
+    }
+}
@@ -0,0 +1,68 @@
+use winnow::{
+    ascii::space0,
+    combinator::{alt, delimited, eof, opt, separated_pair, terminated},
+    token::take_while,
+    PResult, Parser,
+};
+
+use crate::parsers::quoted;
+
+// need to parse:
+// a -> b
+// "node 1" -- c;
+pub fn parse_edges(input: &str) -> PResult<Vec<(String, String)>> {
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(parse_edge)
+        .collect()
+}
+
+fn parse_edge(s: &str) -> PResult<(String, String)> {
+    let input = &mut (&*s);
+    let arrow = delimited(space0, alt(("->", "--")), space0);
+    terminated(
+        separated_pair(parse_node, arrow, parse_node),
+        (space0, opt(';'), space0, eof),
+    )
+    .parse_next(input)
+}
+
+fn parse_node(s: &mut &str) -> PResult<String> {
+    alt((
+        quoted('"', true),
+        take_while(1.., |c: char| c.is_alphanumeric() || c == '_' || c == '.').map(String::from),
+    ))
+    .parse_next(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge(a: &str, b: &str) -> (String, String) {
+        (a.to_string(), b.to_string())
+    }
+
+    #[test]
+    fn parse_directed_edges_should_work() {
+        let ret = parse_edges("a -> b\nb->c;\n  c   ->   a  ").unwrap();
+        assert_eq!(ret, vec![edge("a", "b"), edge("b", "c"), edge("c", "a")]);
+    }
+
+    #[test]
+    fn parse_undirected_edges_should_work() {
+        let ret = parse_edges("a -- b\n\"web server\" -- db").unwrap();
+        assert_eq!(ret, vec![edge("a", "b"), edge("web server", "db")]);
+    }
+
+    #[test]
+    fn parse_edges_with_comments_should_work() {
+        let ret = parse_edges("# dependencies\na -> b\n\n  # b -> c\nb -> d\n").unwrap();
+        assert_eq!(ret, vec![edge("a", "b"), edge("b", "d")]);
+
+        assert!(parse_edges("a => b").is_err());
+        assert!(parse_edges("a -> b -> c").is_err());
+    }
+}
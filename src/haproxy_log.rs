@@ -0,0 +1,136 @@
+use std::net::IpAddr;
+
+use chrono::NaiveDateTime;
+use winnow::{
+    ascii::{dec_int, digit1, space0, space1},
+    combinator::{delimited, opt, preceded, separated_pair, terminated},
+    token::{take_till, take_until},
+    PResult, Parser,
+};
+
+use crate::nginx_log::{
+    parse_ip, parse_method, parse_protocol, parse_url, HttpMethod, HttpProtocol,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct HaproxyLog {
+    pub client_ip: IpAddr,
+    pub client_port: u16,
+    pub accept_date: NaiveDateTime,
+    pub frontend: String,
+    pub backend: String,
+    pub server: String,
+    /// `Tq/Tw/Tc/Tr/Tt` in milliseconds, `-1` when the phase didn't complete.
+    pub timers: [i64; 5],
+    pub status: u16,
+    pub bytes_read: u64,
+    pub method: HttpMethod,
+    pub url: String,
+    pub protocol: HttpProtocol,
+}
+
+// need to parse:
+// haproxy[14389]: 10.0.1.2:33317 [06/Feb/2009:12:14:14.655] http-in static/srv1 10/0/30/69/109 200 2750 - - ---- 1/1/1/1/0 0/0 {1wt.eu} {} "GET /index.html HTTP/1.1"
+pub fn parse_haproxy_log(s: &str) -> PResult<HaproxyLog> {
+    let input = &mut s.trim();
+    // the `process[pid]: ` syslog tag is optional
+    opt((take_till(1.., ['[', ' ']), '[', digit1, "]: ")).parse_next(input)?;
+    let (client_ip, client_port) =
+        terminated(separated_pair(parse_ip, ':', digit1.parse_to()), space1).parse_next(input)?;
+    let accept_date = terminated(
+        delimited('[', take_till(1.., ']'), ']')
+            .try_map(|dt| NaiveDateTime::parse_from_str(dt, "%d/%b/%Y:%H:%M:%S%.3f")),
+        space1,
+    )
+    .parse_next(input)?;
+    let frontend = terminated(take_till(1.., ' '), space1).parse_next(input)?;
+    let (backend, server) = terminated(
+        separated_pair(take_till(1.., '/'), '/', take_till(1.., ' ')),
+        space1,
+    )
+    .parse_next(input)?;
+    let timers = terminated(parse_timers, space1).parse_next(input)?;
+    let status = terminated(digit1.parse_to(), space1).parse_next(input)?;
+    // `+` marks a value logged before the transfer completed
+    let bytes_read = terminated(preceded(opt('+'), digit1.parse_to()), space1).parse_next(input)?;
+    // cookies, termination state, connection counts, queues and captured headers are skipped
+    take_until(0.., '"').parse_next(input)?;
+    let (method, url, protocol) = delimited(
+        '"',
+        (parse_method, parse_url, terminated(parse_protocol, space0)),
+        '"',
+    )
+    .parse_next(input)?;
+    Ok(HaproxyLog {
+        client_ip,
+        client_port,
+        accept_date,
+        frontend: frontend.to_string(),
+        backend: backend.to_string(),
+        server: server.to_string(),
+        timers,
+        status,
+        bytes_read,
+        method,
+        url,
+        protocol,
+    })
+}
+
+fn parse_timers(s: &mut &str) -> PResult<[i64; 5]> {
+    let mut timers = [0; 5];
+    for (i, timer) in timers.iter_mut().enumerate() {
+        if i > 0 {
+            '/'.parse_next(s)?;
+        }
+        *timer = preceded(opt('+'), dec_int).parse_next(s)?;
+    }
+    Ok(timers)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    #[test]
+    fn parse_haproxy_log_should_work() {
+        let s = r#"haproxy[14389]: 10.0.1.2:33317 [06/Feb/2009:12:14:14.655] http-in static/srv1 10/0/30/69/109 200 2750 - - ---- 1/1/1/1/0 0/0 {1wt.eu} {} "GET /index.html HTTP/1.1""#;
+        let ret = parse_haproxy_log(s).unwrap();
+        assert_eq!(
+            ret,
+            HaproxyLog {
+                client_ip: IpAddr::V4(Ipv4Addr::new(10, 0, 1, 2)),
+                client_port: 33317,
+                accept_date: NaiveDate::from_ymd_opt(2009, 2, 6)
+                    .unwrap()
+                    .and_hms_milli_opt(12, 14, 14, 655)
+                    .unwrap(),
+                frontend: "http-in".to_string(),
+                backend: "static".to_string(),
+                server: "srv1".to_string(),
+                timers: [10, 0, 30, 69, 109],
+                status: 200,
+                bytes_read: 2750,
+                method: HttpMethod::Get,
+                url: "/index.html".to_string(),
+                protocol: HttpProtocol::HTTP1_1,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_haproxy_log_with_aborted_phases_should_work() {
+        let s = r#"10.0.1.2:33320 [06/Feb/2009:12:14:14.655] https-in~ www/<NOSRV> 15/-1/-1/-1/+15 503 +212 - - SC-- 0/0/0/0/0 0/0 "POST /login HTTP/1.0""#;
+        let ret = parse_haproxy_log(s).unwrap();
+        assert_eq!(ret.frontend, "https-in~");
+        assert_eq!(ret.server, "<NOSRV>");
+        assert_eq!(ret.timers, [15, -1, -1, -1, 15]);
+        assert_eq!(ret.status, 503);
+        assert_eq!(ret.bytes_read, 212);
+        assert_eq!(ret.method, HttpMethod::Post);
+    }
+}
@@ -0,0 +1,616 @@
+use winnow::{
+    ascii::{dec_uint, hex_uint, line_ending, space0, space1},
+    combinator::{
+        alt, delimited, eof, opt, preceded, repeat, rest, separated, separated_pair, terminated,
+    },
+    error::{ErrMode, ErrorKind, ParserError},
+    stream::AsChar,
+    token::{any, none_of, take, take_till, take_while},
+    PResult, Parser,
+};
+
+use crate::nginx_log::{parse_method, parse_protocol, parse_url, HttpMethod, HttpProtocol};
+
+/// Parse a `Forwarded` header (RFC 7239) into one parameter list per forwarded element.
+pub fn parse_forwarded(s: &str) -> PResult<Vec<Vec<(String, String)>>> {
+    let input = &mut (&*s);
+    let sep_comma = (space0, ',', space0);
+    terminated(
+        separated(1.., parse_forwarded_element, sep_comma),
+        (space0, eof),
+    )
+    .parse_next(input)
+}
+
+fn parse_forwarded_element(s: &mut &str) -> PResult<Vec<(String, String)>> {
+    let sep_semicolon = (space0, ';', space0);
+    separated(1.., parse_forwarded_pair, sep_semicolon).parse_next(s)
+}
+
+fn parse_forwarded_pair(s: &mut &str) -> PResult<(String, String)> {
+    let value = alt((parse_quoted_string, parse_token.map(|v| v.to_string())));
+    separated_pair(parse_token.map(|k| k.to_string()), '=', value).parse_next(s)
+}
+
+/// Parse a `Content-Type` header into its lowercased media type and parameters. Parameter
+/// names are lowercased and quoted values unquoted.
+pub fn parse_content_type(s: &str) -> PResult<(String, Vec<(String, String)>)> {
+    let input = &mut (&*s);
+    let media_type = delimited(space0, (parse_token, '/', parse_token).take(), space0)
+        .map(|t: &str| t.to_ascii_lowercase())
+        .parse_next(input)?;
+    let value = alt((parse_quoted_string, parse_token.map(|v| v.to_string())));
+    let param = separated_pair(parse_token.map(|k| k.to_ascii_lowercase()), '=', value);
+    let params = repeat(0.., preceded((';', space0), terminated(param, space0)));
+    let params = terminated(params, eof).parse_next(input)?;
+    Ok((media_type, params))
+}
+
+/// A link target and its parameters, as found in a `Link` header.
+pub type Link = (String, Vec<(String, String)>);
+
+/// Parse a `Link` header (RFC 8288) into the target URI of each link and its parameters.
+/// Parameter names are lowercased and quoted values unquoted.
+pub fn parse_link_header(s: &str) -> PResult<Vec<Link>> {
+    let input = &mut (&*s);
+    let sep_comma = (space0, ',', space0);
+    delimited(space0, separated(1.., parse_link, sep_comma), (space0, eof)).parse_next(input)
+}
+
+fn parse_link(s: &mut &str) -> PResult<Link> {
+    let uri = delimited('<', take_till(0.., '>'), '>')
+        .map(|uri: &str| uri.to_string())
+        .parse_next(s)?;
+    let value = alt((parse_quoted_string, parse_token.map(|v| v.to_string())));
+    let param = separated_pair(parse_token.map(|k| k.to_ascii_lowercase()), '=', value);
+    let params = repeat(0.., preceded((space0, ';', space0), param)).parse_next(s)?;
+    Ok((uri, params))
+}
+
+/// An authentication challenge from a `WWW-Authenticate` header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Challenge {
+    pub scheme: String,
+    pub params: Vec<(String, String)>,
+}
+
+/// Parse a `WWW-Authenticate` header (RFC 9110) into its challenges. Parameter names are
+/// lowercased and quoted values unquoted.
+pub fn parse_www_authenticate(s: &str) -> PResult<Vec<Challenge>> {
+    let input = &mut (&*s);
+    let sep_comma = (space0, ',', space0);
+    delimited(
+        space0,
+        separated(1.., parse_challenge, sep_comma),
+        (space0, eof),
+    )
+    .parse_next(input)
+}
+
+// challenges and their params are both comma separated, a param list ends at the first
+// item that isn't `name=value`, which then starts the next challenge
+fn parse_challenge(s: &mut &str) -> PResult<Challenge> {
+    let scheme = parse_token.parse_next(s)?;
+    let value = alt((parse_quoted_string, parse_token.map(|v| v.to_string())));
+    let param = separated_pair(
+        parse_token.map(|k| k.to_ascii_lowercase()),
+        (space0, '=', space0),
+        value,
+    );
+    let params = opt(preceded(
+        space1,
+        separated(1.., param, (space0, ',', space0)),
+    ))
+    .parse_next(s)?;
+    Ok(Challenge {
+        scheme: scheme.to_string(),
+        params: params.unwrap_or_default(),
+    })
+}
+
+// need to parse:
+// HTTP/1.1 404 Not Found
+pub fn parse_status_line(s: &str) -> PResult<(HttpProtocol, u16, String)> {
+    let input = &mut s.trim_end_matches(['\r', '\n']);
+    let protocol = terminated(parse_protocol, space1).parse_next(input)?;
+    let code = take_while(3, AsChar::is_dec_digit)
+        .parse_to::<u16>()
+        .verify(|code| (100..=599).contains(code))
+        .parse_next(input)?;
+    // the reason phrase is optional and may contain spaces
+    let reason = terminated(opt(preceded(' ', rest)), eof).parse_next(input)?;
+    Ok((protocol, code, reason.unwrap_or_default().to_string()))
+}
+
+/// An HTTP/1.x request with its headers in the order they were sent.
+#[derive(Debug, PartialEq, Eq)]
+pub struct HttpRequest {
+    pub method: HttpMethod,
+    pub url: String,
+    pub protocol: HttpProtocol,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+// need to parse:
+// POST /api HTTP/1.1\r\nHost: example.com\r\nContent-Length: 2\r\n\r\nhi
+pub fn parse_http_request(input: &str) -> PResult<HttpRequest> {
+    let input = &mut (&*input);
+    let (method, url, protocol) = terminated(
+        (parse_method, parse_url, parse_protocol),
+        (space0, line_ending),
+    )
+    .parse_next(input)?;
+    let headers: Vec<(String, String)> =
+        terminated(repeat(0.., parse_header), line_ending).parse_next(input)?;
+    // without a Content-Length there is no body
+    let body = match headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("content-length"))
+    {
+        Some((_, len)) => {
+            let len = len
+                .parse::<usize>()
+                .map_err(|_| ErrMode::from_error_kind(input, ErrorKind::Verify))?;
+            // the length counts bytes, so it must also land on a char boundary
+            let body = input
+                .get(..len)
+                .ok_or_else(|| ErrMode::from_error_kind(input, ErrorKind::Eof))?;
+            *input = &input[len..];
+            Some(body.to_string())
+        }
+        None => None,
+    };
+    eof.parse_next(input)?;
+    Ok(HttpRequest {
+        method,
+        url,
+        protocol,
+        headers,
+        body,
+    })
+}
+
+fn parse_header(s: &mut &str) -> PResult<(String, String)> {
+    let value = take_till(0.., ['\r', '\n']).map(|v: &str| v.trim_end().to_string());
+    terminated(
+        separated_pair(parse_token.map(str::to_string), (':', space0), value),
+        line_ending,
+    )
+    .parse_next(s)
+}
+
+/// A `Content-Range` header, `start` and `end` are `None` for an unsatisfied range and
+/// `total` is `None` when the complete length is unknown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentRange {
+    pub start: Option<u64>,
+    pub end: Option<u64>,
+    pub total: Option<u64>,
+}
+
+// need to parse:
+// bytes 200-1023/2048 or bytes 200-1023/* or bytes */2048
+pub fn parse_content_range(s: &str) -> PResult<ContentRange> {
+    let input = &mut s.trim();
+    "bytes ".parse_next(input)?;
+    let range = alt((
+        '*'.value(None),
+        separated_pair(dec_uint, '-', dec_uint)
+            .verify(|(start, end): &(u64, u64)| start <= end)
+            .map(Some),
+    ))
+    .parse_next(input)?;
+    let total =
+        delimited('/', alt(('*'.value(None), dec_uint.map(Some))), eof).parse_next(input)?;
+    // an unsatisfied range must still report the complete length
+    if range.is_none() && total.is_none() {
+        return Err(ErrMode::from_error_kind(input, ErrorKind::Verify));
+    }
+    Ok(ContentRange {
+        start: range.map(|(start, _)| start),
+        end: range.map(|(_, end)| end),
+        total,
+    })
+}
+
+/// A cookie and its attributes as sent in a `Set-Cookie` header (RFC 6265).
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SetCookie {
+    pub name: String,
+    pub value: String,
+    pub path: Option<String>,
+    pub domain: Option<String>,
+    pub max_age: Option<i64>,
+    pub secure: bool,
+    pub http_only: bool,
+    pub same_site: Option<String>,
+}
+
+/// Parse a `Set-Cookie` header value. Attribute names are matched case-insensitively,
+/// unknown and empty attributes, e.g. from a trailing `;`, are ignored.
+pub fn parse_set_cookie(s: &str) -> PResult<SetCookie> {
+    let input = &mut (&*s);
+    let (name, value) = separated_pair(
+        delimited(space0, parse_token, space0),
+        '=',
+        take_till(0.., ';').map(str::trim),
+    )
+    .parse_next(input)?;
+    let attrs: Vec<(&str, Option<&str>)> =
+        terminated(repeat(0.., parse_cookie_attr), eof).parse_next(input)?;
+
+    let mut cookie = SetCookie {
+        name: name.to_string(),
+        value: value.to_string(),
+        ..Default::default()
+    };
+    for (k, v) in attrs {
+        match (k.to_ascii_lowercase().as_str(), v) {
+            ("path", Some(v)) => cookie.path = Some(v.to_string()),
+            ("domain", Some(v)) => cookie.domain = Some(v.to_string()),
+            // an invalid Max-Age is ignored rather than rejecting the whole cookie
+            ("max-age", Some(v)) => cookie.max_age = v.parse().ok().or(cookie.max_age),
+            ("samesite", Some(v)) => cookie.same_site = Some(v.to_string()),
+            ("secure", _) => cookie.secure = true,
+            ("httponly", _) => cookie.http_only = true,
+            _ => {}
+        }
+    }
+    Ok(cookie)
+}
+
+fn parse_cookie_attr<'a>(s: &mut &'a str) -> PResult<(&'a str, Option<&'a str>)> {
+    let name = take_till(0.., ['=', ';']).map(str::trim);
+    let value = preceded('=', take_till(0.., ';').map(str::trim));
+    preceded(';', (name, opt(value))).parse_next(s)
+}
+
+/// Decode a chunked transfer-encoding body, ignoring chunk extensions and trailers.
+pub fn parse_chunked(body: &str) -> PResult<Vec<u8>> {
+    let input = &mut body.as_bytes();
+    let chunks: Vec<&[u8]> = repeat(0.., parse_chunk).parse_next(input)?;
+    parse_last_chunk(input)?;
+    Ok(chunks.concat())
+}
+
+fn parse_chunk<'a>(s: &mut &'a [u8]) -> PResult<&'a [u8]> {
+    let size = parse_chunk_size.verify(|size| *size > 0).parse_next(s)?;
+    terminated(take(size), "\r\n").parse_next(s)
+}
+
+fn parse_last_chunk(s: &mut &[u8]) -> PResult<()> {
+    parse_chunk_size.verify(|size| *size == 0).parse_next(s)?;
+    let trailer = (take_till(1.., '\r'), "\r\n");
+    (repeat(0.., trailer).map(|()| ()), "\r\n", eof)
+        .void()
+        .parse_next(s)
+}
+
+// the chunk size is followed by optional `;name=value` extensions which we don't care about
+fn parse_chunk_size(s: &mut &[u8]) -> PResult<usize> {
+    let ext = repeat(0.., (';', take_till(0.., [';', '\r']))).map(|()| ());
+    terminated(hex_uint::<_, u64, _>, (ext, "\r\n"))
+        .try_map(usize::try_from)
+        .parse_next(s)
+}
+
+fn parse_token<'a>(s: &mut &'a str) -> PResult<&'a str> {
+    take_while(1.., is_tchar).parse_next(s)
+}
+
+fn parse_quoted_string(s: &mut &str) -> PResult<String> {
+    let quoted_char = alt((preceded('\\', any), none_of(['"', '\\'])));
+    delimited('"', repeat(0.., quoted_char), '"').parse_next(s)
+}
+
+// token characters as defined in RFC 7230
+fn is_tchar(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_forwarded_should_work() {
+        let ret = parse_forwarded("for=192.0.2.60;proto=http;by=203.0.113.43").unwrap();
+        assert_eq!(
+            ret,
+            vec![vec![
+                ("for".to_string(), "192.0.2.60".to_string()),
+                ("proto".to_string(), "http".to_string()),
+                ("by".to_string(), "203.0.113.43".to_string()),
+            ]]
+        );
+    }
+
+    #[test]
+    fn parse_forwarded_with_multiple_elements_should_work() {
+        let ret = parse_forwarded(r#"for=192.0.2.60;proto=http, for="[2001:db8:cafe::17]:4711""#)
+            .unwrap();
+        assert_eq!(
+            ret,
+            vec![
+                vec![
+                    ("for".to_string(), "192.0.2.60".to_string()),
+                    ("proto".to_string(), "http".to_string()),
+                ],
+                vec![("for".to_string(), "[2001:db8:cafe::17]:4711".to_string())],
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_chunked_should_work() {
+        let body = "4\r\nWiki\r\n5;name=value\r\npedia\r\n0\r\n\r\n";
+        assert_eq!(parse_chunked(body).unwrap(), b"Wikipedia");
+    }
+
+    #[test]
+    fn parse_chunked_with_only_last_chunk_should_work() {
+        assert_eq!(parse_chunked("0\r\n\r\n").unwrap(), b"");
+        assert_eq!(parse_chunked("0\r\nExpires: never\r\n\r\n").unwrap(), b"");
+        assert!(parse_chunked("4\r\nWiki\r\n").is_err());
+    }
+
+    #[test]
+    fn parse_set_cookie_should_work() {
+        let ret = parse_set_cookie("sid=31d4d96e407aad42; Path=/; Max-Age=3600; Secure; HttpOnly")
+            .unwrap();
+        assert_eq!(
+            ret,
+            SetCookie {
+                name: "sid".to_string(),
+                value: "31d4d96e407aad42".to_string(),
+                path: Some("/".to_string()),
+                max_age: Some(3600),
+                secure: true,
+                http_only: true,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn parse_set_cookie_attributes_should_be_case_insensitive() {
+        let ret = parse_set_cookie(
+            "lang=en-US; domain=example.com; SAMESITE=Lax; secure; Foo=bar; max-age=abc",
+        )
+        .unwrap();
+        assert_eq!(ret.domain.as_deref(), Some("example.com"));
+        assert_eq!(ret.same_site.as_deref(), Some("Lax"));
+        assert!(ret.secure);
+        assert!(!ret.http_only);
+        assert_eq!(ret.max_age, None);
+
+        assert!(parse_set_cookie("=value; Path=/").is_err());
+    }
+
+    #[test]
+    fn parse_set_cookie_with_empty_attributes_should_work() {
+        let ret = parse_set_cookie("a=b; Path=/;").unwrap();
+        assert_eq!(ret.value, "b");
+        assert_eq!(ret.path.as_deref(), Some("/"));
+
+        let ret = parse_set_cookie("a=b;; HttpOnly ; ").unwrap();
+        assert!(ret.http_only);
+    }
+
+    #[test]
+    fn parse_status_line_should_work() {
+        let ret = parse_status_line("HTTP/1.1 404 Not Found\r\n").unwrap();
+        assert_eq!(ret, (HttpProtocol::HTTP1_1, 404, "Not Found".to_string()));
+    }
+
+    #[test]
+    fn parse_status_line_without_reason_should_work() {
+        let ret = parse_status_line("HTTP/2.0 204").unwrap();
+        assert_eq!(ret, (HttpProtocol::HTTP2_0, 204, "".to_string()));
+
+        let ret = parse_status_line("HTTP/1.0 200 ").unwrap();
+        assert_eq!(ret, (HttpProtocol::HTTP1_0, 200, "".to_string()));
+    }
+
+    #[test]
+    fn parse_status_line_code_boundary_should_work() {
+        let ret = parse_status_line("HTTP/1.1 599 Custom").unwrap();
+        assert_eq!(ret.1, 599);
+
+        assert!(parse_status_line("HTTP/1.1 600 Nope").is_err());
+        assert!(parse_status_line("HTTP/1.1 99 Nope").is_err());
+        assert!(parse_status_line("HTTP/1.1 2000 Nope").is_err());
+        assert!(parse_status_line("HTTP/1.1404 Not Found").is_err());
+    }
+
+    #[test]
+    fn parse_content_type_should_work() {
+        let ret = parse_content_type("Text/HTML; Charset=utf-8").unwrap();
+        assert_eq!(
+            ret,
+            (
+                "text/html".to_string(),
+                vec![("charset".to_string(), "utf-8".to_string())]
+            )
+        );
+
+        let ret = parse_content_type("application/json").unwrap();
+        assert_eq!(ret, ("application/json".to_string(), vec![]));
+    }
+
+    #[test]
+    fn parse_content_type_with_quoted_boundary_should_work() {
+        let ret =
+            parse_content_type(r#"multipart/form-data; charset=utf-8; boundary="x y""#).unwrap();
+        assert_eq!(
+            ret,
+            (
+                "multipart/form-data".to_string(),
+                vec![
+                    ("charset".to_string(), "utf-8".to_string()),
+                    ("boundary".to_string(), "x y".to_string()),
+                ]
+            )
+        );
+
+        assert!(parse_content_type("text").is_err());
+        assert!(parse_content_type("text/html; charset").is_err());
+    }
+
+    #[test]
+    fn parse_link_header_should_work() {
+        let ret =
+            parse_link_header(r#"<https://api/next>; rel="next", <https://api/last>; rel="last""#)
+                .unwrap();
+        assert_eq!(
+            ret,
+            vec![
+                (
+                    "https://api/next".to_string(),
+                    vec![("rel".to_string(), "next".to_string())]
+                ),
+                (
+                    "https://api/last".to_string(),
+                    vec![("rel".to_string(), "last".to_string())]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_link_header_with_multiple_params_should_work() {
+        let ret =
+            parse_link_header(r#"<https://api/items?page=2>; Rel=next; title="Page 2, items""#)
+                .unwrap();
+        assert_eq!(
+            ret,
+            vec![(
+                "https://api/items?page=2".to_string(),
+                vec![
+                    ("rel".to_string(), "next".to_string()),
+                    ("title".to_string(), "Page 2, items".to_string()),
+                ]
+            )]
+        );
+        assert!(parse_link_header("https://api/next; rel=next").is_err());
+    }
+
+    #[test]
+    fn parse_content_range_should_work() {
+        assert_eq!(
+            parse_content_range("bytes 200-1023/2048").unwrap(),
+            ContentRange {
+                start: Some(200),
+                end: Some(1023),
+                total: Some(2048),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_content_range_with_unknown_total_should_work() {
+        assert_eq!(
+            parse_content_range("bytes 200-1023/*").unwrap(),
+            ContentRange {
+                start: Some(200),
+                end: Some(1023),
+                total: None,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_unsatisfied_content_range_should_work() {
+        assert_eq!(
+            parse_content_range("bytes */2048").unwrap(),
+            ContentRange {
+                start: None,
+                end: None,
+                total: Some(2048),
+            }
+        );
+        assert!(parse_content_range("bytes */*").is_err());
+        assert!(parse_content_range("bytes 1023-200/2048").is_err());
+        assert!(parse_content_range("items 0-1/2").is_err());
+    }
+
+    #[test]
+    fn parse_http_request_without_body_should_work() {
+        let ret = parse_http_request(
+            "GET /index.html?q=1 HTTP/1.1\r\nHost: example.com\r\nAccept:  */*\r\n\r\n",
+        )
+        .unwrap();
+        assert_eq!(
+            ret,
+            HttpRequest {
+                method: HttpMethod::Get,
+                url: "/index.html?q=1".to_string(),
+                protocol: HttpProtocol::HTTP1_1,
+                headers: vec![
+                    ("Host".to_string(), "example.com".to_string()),
+                    ("Accept".to_string(), "*/*".to_string()),
+                ],
+                body: None,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_http_request_with_body_should_work() {
+        let ret = parse_http_request(
+            "POST /api HTTP/1.0\r\ncontent-length: 11\r\nContent-Type: application/json\r\n\r\n{\"a\": \"\u{e9}\"}",
+        )
+        .unwrap();
+        assert_eq!(ret.method, HttpMethod::Post);
+        assert_eq!(ret.protocol, HttpProtocol::HTTP1_0);
+        assert_eq!(ret.headers.len(), 2);
+        assert_eq!(ret.body.as_deref(), Some("{\"a\": \"\u{e9}\"}"));
+
+        assert!(parse_http_request("POST /api HTTP/1.1\r\nContent-Length: 5\r\n\r\nhi").is_err());
+        assert!(parse_http_request("POST /api HTTP/1.1\r\nContent-Length: x\r\n\r\n").is_err());
+        assert!(parse_http_request("GET / HTTP/1.1\r\n\r\nextra").is_err());
+    }
+
+    #[test]
+    fn parse_www_authenticate_should_work() {
+        let ret = parse_www_authenticate(r#"Basic realm="app""#).unwrap();
+        assert_eq!(
+            ret,
+            vec![Challenge {
+                scheme: "Basic".to_string(),
+                params: vec![("realm".to_string(), "app".to_string())],
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_www_authenticate_with_multiple_challenges_should_work() {
+        let ret = parse_www_authenticate(
+            r#"Basic realm="app", Bearer realm="api", error="invalid_token", Negotiate"#,
+        )
+        .unwrap();
+        assert_eq!(
+            ret,
+            vec![
+                Challenge {
+                    scheme: "Basic".to_string(),
+                    params: vec![("realm".to_string(), "app".to_string())],
+                },
+                Challenge {
+                    scheme: "Bearer".to_string(),
+                    params: vec![
+                        ("realm".to_string(), "api".to_string()),
+                        ("error".to_string(), "invalid_token".to_string()),
+                    ],
+                },
+                Challenge {
+                    scheme: "Negotiate".to_string(),
+                    params: vec![],
+                },
+            ]
+        );
+        assert!(parse_www_authenticate("").is_err());
+        assert!(parse_www_authenticate(r#"Basic realm="app"#).is_err());
+    }
+}
@@ -0,0 +1,91 @@
+use winnow::{
+    combinator::{alt, delimited, eof, preceded, repeat, rest, separated_pair},
+    token::{take_till, take_while},
+    PResult, Parser,
+};
+
+/// A content line of an iCalendar (RFC 5545) document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ICalProp {
+    pub name: String,
+    pub params: Vec<(String, String)>,
+    pub value: String,
+}
+
+// need to parse:
+// DTSTART;TZID=America/New_York:20230101T090000
+pub fn parse_ical_line(s: &str) -> PResult<ICalProp> {
+    let input = &mut s.trim_end_matches(['\r', '\n']);
+    let name = parse_name.parse_next(input)?;
+    let params = repeat(0.., preceded(';', parse_param)).parse_next(input)?;
+    let value = delimited(':', rest, eof).parse_next(input)?;
+    Ok(ICalProp {
+        name: name.to_string(),
+        params,
+        value: value.to_string(),
+    })
+}
+
+fn parse_name<'a>(s: &mut &'a str) -> PResult<&'a str> {
+    take_while(1.., |c: char| c.is_ascii_alphanumeric() || c == '-').parse_next(s)
+}
+
+// quoted values may contain `;`, `:` and `,`, the quotes are dropped
+fn parse_param(s: &mut &str) -> PResult<(String, String)> {
+    let value = alt((
+        delimited('"', take_till(0.., '"'), '"'),
+        take_till(0.., [';', ':', '"']),
+    ));
+    separated_pair(
+        parse_name.map(str::to_string),
+        '=',
+        value.map(str::to_string),
+    )
+    .parse_next(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ical_line_with_params_should_work() {
+        let ret = parse_ical_line("DTSTART;TZID=America/New_York:20230101T090000").unwrap();
+        assert_eq!(
+            ret,
+            ICalProp {
+                name: "DTSTART".to_string(),
+                params: vec![("TZID".to_string(), "America/New_York".to_string())],
+                value: "20230101T090000".to_string(),
+            }
+        );
+
+        let ret =
+            parse_ical_line("ATTENDEE;ROLE=CHAIR;CN=\"Doe; John\":mailto:john@example.com\r\n")
+                .unwrap();
+        assert_eq!(ret.name, "ATTENDEE");
+        assert_eq!(
+            ret.params,
+            vec![
+                ("ROLE".to_string(), "CHAIR".to_string()),
+                ("CN".to_string(), "Doe; John".to_string()),
+            ]
+        );
+        assert_eq!(ret.value, "mailto:john@example.com");
+    }
+
+    #[test]
+    fn parse_ical_line_without_params_should_work() {
+        let ret = parse_ical_line("SUMMARY:Team meeting: Q1 planning").unwrap();
+        assert_eq!(
+            ret,
+            ICalProp {
+                name: "SUMMARY".to_string(),
+                params: vec![],
+                value: "Team meeting: Q1 planning".to_string(),
+            }
+        );
+        assert!(parse_ical_line("SUMMARY Team meeting").is_err());
+        assert!(parse_ical_line(":no name").is_err());
+    }
+}
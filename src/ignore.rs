@@ -0,0 +1,111 @@
+use winnow::PResult;
+
+use crate::glob::{parse_glob, Glob};
+
+/// A set of `.gitignore` rules, later rules take precedence over earlier ones.
+#[derive(Debug, Clone)]
+pub struct GitIgnore {
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    glob: Glob,
+    negated: bool,
+    dir_only: bool,
+}
+
+// need to parse:
+// # build output
+// target/
+// *.log
+// !important.log
+pub fn parse_gitignore(s: &str) -> PResult<GitIgnore> {
+    let rules = s
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(parse_rule)
+        .collect::<PResult<_>>()?;
+    Ok(GitIgnore { rules })
+}
+
+fn parse_rule(line: &str) -> PResult<Rule> {
+    let (negated, pattern) = match line.strip_prefix('!') {
+        Some(pattern) => (true, pattern),
+        None => (false, line.strip_prefix('\\').unwrap_or(line)),
+    };
+    let (dir_only, pattern) = match pattern.strip_suffix('/') {
+        Some(pattern) => (true, pattern),
+        None => (false, pattern),
+    };
+    // a pattern without a slash matches at any depth, otherwise it's relative to the root
+    let glob = match pattern.strip_prefix('/') {
+        Some(pattern) => parse_glob(pattern)?,
+        None if pattern.contains('/') => parse_glob(pattern)?,
+        None => parse_glob(&format!("**/{}", pattern))?,
+    };
+    Ok(Rule {
+        glob,
+        negated,
+        dir_only,
+    })
+}
+
+impl GitIgnore {
+    pub fn is_ignored(&self, path: &str, is_dir: bool) -> bool {
+        let path = path.trim_matches('/');
+        // nothing inside an ignored directory can be re-included
+        let parent_ignored = path
+            .match_indices('/')
+            .any(|(i, _)| self.matched(&path[..i], true) == Some(true));
+        parent_ignored || self.matched(path, is_dir).unwrap_or(false)
+    }
+
+    // the last matching rule wins, `None` if no rule matches
+    fn matched(&self, path: &str, is_dir: bool) -> Option<bool> {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| (is_dir || !rule.dir_only) && rule.glob.matches(path))
+            .map(|rule| !rule.negated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gitignore_pattern_should_work() {
+        let ignore = parse_gitignore("# logs\n*.log\n\n/build.rs\n").unwrap();
+        assert!(ignore.is_ignored("debug.log", false));
+        assert!(ignore.is_ignored("logs/app/debug.log", false));
+        assert!(!ignore.is_ignored("debug.txt", false));
+        assert!(ignore.is_ignored("build.rs", false));
+        assert!(!ignore.is_ignored("src/build.rs", false));
+    }
+
+    #[test]
+    fn gitignore_negation_should_work() {
+        let ignore = parse_gitignore("*.log\n!important.log\n").unwrap();
+        assert!(ignore.is_ignored("debug.log", false));
+        assert!(!ignore.is_ignored("important.log", false));
+        assert!(!ignore.is_ignored("logs/important.log", false));
+
+        // last match wins
+        let ignore = parse_gitignore("!important.log\n*.log\n").unwrap();
+        assert!(ignore.is_ignored("important.log", false));
+    }
+
+    #[test]
+    fn gitignore_dir_only_should_work() {
+        let ignore = parse_gitignore("target/\n!target/keep.txt\n").unwrap();
+        assert!(ignore.is_ignored("target", true));
+        assert!(!ignore.is_ignored("target", false));
+        assert!(ignore.is_ignored("target/debug/grammar", false));
+        assert!(ignore.is_ignored("crates/foo/target", true));
+        // a file inside an ignored directory can't be re-included
+        assert!(ignore.is_ignored("target/keep.txt", false));
+    }
+}
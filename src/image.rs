@@ -0,0 +1,116 @@
+use winnow::{
+    combinator::{eof, opt, preceded, separated, terminated},
+    stream::AsChar,
+    token::{take_till, take_while},
+    PResult, Parser,
+};
+
+/// A Docker/OCI image reference such as `docker.io/library/nginx:1.25`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageRef {
+    pub registry: Option<String>,
+    pub repository: String,
+    pub tag: Option<String>,
+    pub digest: Option<String>,
+}
+
+// need to parse:
+// registry.example.com:5000/library/nginx:1.25@sha256:0123...
+pub fn parse_image_ref(s: &str) -> PResult<ImageRef> {
+    let input = &mut s.trim();
+    // the first component is a registry only if it looks like a host
+    let registry = opt(terminated(
+        take_till(1.., ['/', '@']).verify(|c: &str| c.contains(['.', ':']) || c == "localhost"),
+        '/',
+    ))
+    .parse_next(input)?;
+    let component = take_while(1.., |c: char| {
+        c.is_ascii_lowercase() || c.is_ascii_digit() || "._-".contains(c)
+    });
+    let repository = separated(1.., component, '/')
+        .map(|()| ())
+        .take()
+        .parse_next(input)?;
+    let tag = opt(preceded(
+        ':',
+        take_while(1..=128, |c: char| {
+            c.is_ascii_alphanumeric() || "._-".contains(c)
+        }),
+    ))
+    .parse_next(input)?;
+    let digest = opt(preceded(
+        '@',
+        (
+            take_while(1.., |c: char| {
+                c.is_ascii_alphanumeric() || "+._-".contains(c)
+            }),
+            ':',
+            take_while(32.., AsChar::is_hex_digit),
+        )
+            .take(),
+    ))
+    .parse_next(input)?;
+    eof.parse_next(input)?;
+    Ok(ImageRef {
+        registry: registry.map(str::to_string),
+        repository: repository.to_string(),
+        tag: tag.map(str::to_string),
+        digest: digest.map(str::to_string),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIGEST: &str = "sha256:0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+    #[test]
+    fn parse_full_image_ref_should_work() {
+        let s = format!("registry.example.com:5000/library/nginx:1.25@{}", DIGEST);
+        assert_eq!(
+            parse_image_ref(&s).unwrap(),
+            ImageRef {
+                registry: Some("registry.example.com:5000".to_string()),
+                repository: "library/nginx".to_string(),
+                tag: Some("1.25".to_string()),
+                digest: Some(DIGEST.to_string()),
+            }
+        );
+
+        let ret = parse_image_ref("localhost/app").unwrap();
+        assert_eq!(ret.registry.as_deref(), Some("localhost"));
+        let ret = parse_image_ref("library/nginx").unwrap();
+        assert_eq!(ret.registry, None);
+        assert_eq!(ret.repository, "library/nginx");
+    }
+
+    #[test]
+    fn parse_bare_image_ref_should_work() {
+        assert_eq!(
+            parse_image_ref("nginx").unwrap(),
+            ImageRef {
+                registry: None,
+                repository: "nginx".to_string(),
+                tag: None,
+                digest: None,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_tagged_image_ref_should_work() {
+        assert_eq!(
+            parse_image_ref("nginx:latest").unwrap(),
+            ImageRef {
+                registry: None,
+                repository: "nginx".to_string(),
+                tag: Some("latest".to_string()),
+                digest: None,
+            }
+        );
+        assert!(parse_image_ref("Nginx").is_err());
+        assert!(parse_image_ref("nginx:").is_err());
+        assert!(parse_image_ref("nginx@sha256:abc").is_err());
+    }
+}
@@ -0,0 +1,78 @@
+use winnow::{
+    ascii::{alphanumeric1, space0, space1},
+    combinator::{eof, opt, preceded, repeat, rest, terminated},
+    token::take_till,
+    PResult, Parser,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct IrcMessage {
+    pub prefix: Option<String>,
+    pub command: String,
+    pub params: Vec<String>,
+}
+
+// need to parse:
+// :nick!user@host PRIVMSG #chan :hello world
+pub fn parse_irc_message(s: &str) -> PResult<IrcMessage> {
+    let input = &mut s.trim_end_matches(['\r', '\n']);
+    let prefix = opt(terminated(preceded(':', take_till(1.., ' ')), space1)).parse_next(input)?;
+    let command = alphanumeric1.parse_next(input)?;
+    let mut params: Vec<String> = repeat(0.., preceded(space1, parse_middle)).parse_next(input)?;
+    // the trailing param starts with `:` and runs to the end of the line, spaces included
+    let trailing =
+        terminated(opt(preceded((space1, ':'), rest)), (space0, eof)).parse_next(input)?;
+    params.extend(trailing.map(String::from));
+    Ok(IrcMessage {
+        prefix: prefix.map(String::from),
+        command: command.to_string(),
+        params,
+    })
+}
+
+fn parse_middle(s: &mut &str) -> PResult<String> {
+    take_till(1.., ' ')
+        .verify(|p: &str| !p.starts_with(':'))
+        .map(String::from)
+        .parse_next(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_privmsg_should_work() {
+        let ret = parse_irc_message(":nick!user@host PRIVMSG #chan :hello world\r\n").unwrap();
+        assert_eq!(
+            ret,
+            IrcMessage {
+                prefix: Some("nick!user@host".to_string()),
+                command: "PRIVMSG".to_string(),
+                params: vec!["#chan".to_string(), "hello world".to_string()],
+            }
+        );
+
+        let ret = parse_irc_message(":irc.example.com 001 nick :Welcome :)").unwrap();
+        assert_eq!(ret.command, "001");
+        assert_eq!(ret.params, vec!["nick", "Welcome :)"]);
+    }
+
+    #[test]
+    fn parse_command_without_prefix_should_work() {
+        let ret = parse_irc_message("JOIN #rust,#winnow").unwrap();
+        assert_eq!(
+            ret,
+            IrcMessage {
+                prefix: None,
+                command: "JOIN".to_string(),
+                params: vec!["#rust,#winnow".to_string()],
+            }
+        );
+
+        let ret = parse_irc_message("PING :").unwrap();
+        assert_eq!(ret.params, vec![""]);
+
+        assert!(parse_irc_message(":only.prefix").is_err());
+    }
+}
@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+
+use winnow::{
+    binary::le_u64,
+    combinator::{alt, eof, opt, repeat, separated, terminated},
+    token::{take, take_till, take_while},
+    PResult, Parser,
+};
+
+/// Parse the systemd journal export format into one field map per record. Binary fields are
+/// decoded as UTF-8 with invalid sequences replaced, and a repeated field keeps its last value.
+pub fn parse_journal_export(input: &[u8]) -> PResult<Vec<HashMap<String, String>>> {
+    let input = &mut (&*input);
+    // records are separated by a blank line, the final one may or may not be followed by it
+    terminated(separated(0.., parse_record, '\n'), (opt('\n'), eof)).parse_next(input)
+}
+
+fn parse_record(s: &mut &[u8]) -> PResult<HashMap<String, String>> {
+    repeat(1.., alt((parse_text_field, parse_binary_field))).parse_next(s)
+}
+
+// FIELD=value\n
+fn parse_text_field(s: &mut &[u8]) -> PResult<(String, String)> {
+    let name = terminated(parse_field_name, '=').parse_next(s)?;
+    let value = terminated(take_till(0.., '\n'), '\n').parse_next(s)?;
+    Ok((name, String::from_utf8_lossy(value).into_owned()))
+}
+
+// FIELD\n, then the value's length as a little-endian u64, the value itself and \n
+fn parse_binary_field(s: &mut &[u8]) -> PResult<(String, String)> {
+    let name = terminated(parse_field_name, '\n').parse_next(s)?;
+    let len = le_u64.try_map(usize::try_from).parse_next(s)?;
+    let value = terminated(take(len), '\n').parse_next(s)?;
+    Ok((name, String::from_utf8_lossy(value).into_owned()))
+}
+
+fn parse_field_name(s: &mut &[u8]) -> PResult<String> {
+    take_while(1.., |b: u8| b.is_ascii_alphanumeric() || b == b'_')
+        .map(|name: &[u8]| String::from_utf8_lossy(name).into_owned())
+        .parse_next(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_journal_export_should_work() {
+        let input =
+            b"__CURSOR=s=1;i=1\n_PID=42\nMESSAGE=started\n\n__CURSOR=s=1;i=2\nMESSAGE=a=b\n\n";
+        let ret = parse_journal_export(input).unwrap();
+        assert_eq!(ret.len(), 2);
+        assert_eq!(ret[0]["__CURSOR"], "s=1;i=1");
+        assert_eq!(ret[0]["_PID"], "42");
+        assert_eq!(ret[0]["MESSAGE"], "started");
+        assert_eq!(ret[1]["MESSAGE"], "a=b");
+        assert_eq!(ret[1].len(), 2);
+
+        // the trailing blank line is optional
+        let ret = parse_journal_export(b"MESSAGE=only\n").unwrap();
+        assert_eq!(ret.len(), 1);
+        assert!(parse_journal_export(b"MESSAGE=no newline").is_err());
+    }
+
+    #[test]
+    fn parse_journal_export_with_binary_field_should_work() {
+        let mut input = b"_PID=42\nMESSAGE\n".to_vec();
+        let message = "line one\nline two";
+        input.extend_from_slice(&(message.len() as u64).to_le_bytes());
+        input.extend_from_slice(message.as_bytes());
+        input.extend_from_slice(b"\nPRIORITY=6\n\n");
+
+        let ret = parse_journal_export(&input).unwrap();
+        assert_eq!(ret.len(), 1);
+        assert_eq!(ret[0]["MESSAGE"], message);
+        assert_eq!(ret[0]["PRIORITY"], "6");
+    }
+}
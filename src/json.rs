@@ -1,25 +1,54 @@
-use std::collections::HashMap;
+mod canonical;
+mod patch;
+mod path;
+mod pointer;
+mod recover;
+mod ser;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod stream;
+
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt,
+    ops::Range,
+};
 
 use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
 use winnow::{
     ascii::{digit1, multispace0},
-    combinator::{alt, delimited, opt, separated, separated_pair, trace},
-    error::{ContextError, ErrMode, ParserError},
+    combinator::{alt, cut_err, delimited, opt, preceded, separated, terminated},
+    error::{ContextError, ErrMode, ErrorKind, ParserError},
     prelude::*,
-    stream::{AsChar, Stream, StreamIsPartial},
-    token::take_until,
+    token::{one_of, take_till, take_until, take_while},
 };
 
-#[allow(unused)]
+use crate::parsers::{line_column, quoted, snippet};
+
+pub use canonical::CanonicalJson;
+pub use patch::{apply_patch, merge_patch, PatchOp};
+pub use pointer::{assert_json_eq, seek_pointer, PointerMode};
+pub use recover::{parse_json_recover, ParseError};
+pub use ser::NumberFormat;
+pub use stream::stream_array;
+
+#[cfg(test)]
+thread_local! {
+    // counts materialized values so tests can tell which subtrees were skipped
+    static PARSED_VALUES: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
 #[derive(Debug, Clone, PartialEq)]
-enum Num {
+pub enum Num {
     Int(i64),
     Float(f64),
 }
 
-#[allow(unused)]
 #[derive(Debug, Clone, PartialEq)]
-enum JsonValue {
+pub enum JsonValue {
     Null,
     Bool(bool),
     Number(Num),
@@ -28,48 +57,540 @@ enum JsonValue {
     Object(HashMap<String, JsonValue>),
 }
 
-fn main() -> Result<()> {
-    let s = r#"{
-        "name": "John Doe",
-        "age": 43,
-        "is_student": false,
-        "marks": [87.0, 90, -45.7, 67.9],
-        "address": {
-            "city": "New York",
-            "zip": 10001
+/// The kind of a `JsonValue`, without its payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonType {
+    Null,
+    Bool,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+impl Num {
+    /// Return the number as an `f64`, possibly losing precision for large integers.
+    pub fn as_f64(&self) -> f64 {
+        match self {
+            Num::Int(v) => *v as f64,
+            Num::Float(v) => *v,
         }
-    }"#;
+    }
 
-    let input = &mut (&*s);
-    let json = parse_json(input)
-        .map_err(|e: ErrMode<ContextError>| anyhow!("Failed to parse JSON: {:?}", e));
+    /// Whether the number has no fractional part.
+    pub fn is_integer(&self) -> bool {
+        match self {
+            Num::Int(_) => true,
+            Num::Float(v) => v.is_finite() && v.fract() == 0.0,
+        }
+    }
 
-    println!("{:#?}", json);
+    /// Downgrade a `Float` to an `Int` when the conversion is exact, e.g. `3.0` to `3`.
+    /// `-0.0` stays a `Float` so the sign is not lost.
+    pub fn normalize(self) -> Num {
+        match self {
+            Num::Float(v)
+                if self.is_integer()
+                    && !(v == 0.0 && v.is_sign_negative())
+                    && v >= i64::MIN as f64
+                    && v < i64::MAX as f64 =>
+            {
+                Num::Int(v as i64)
+            }
+            n => n,
+        }
+    }
+}
 
-    Ok(())
+/// Formats the number as a JSON literal. Finite values are read back unchanged by
+/// [`parse_num`], integral floats keep a `.0` so they stay floats. JSON has no `NaN` or
+/// infinities, those are written as `null`, which doesn't parse back as a number. This is
+/// independent of the serializer, whose default [`NumberFormat`] writes `1.0` as `1` unless
+/// `float_trailing_zero` is set.
+impl fmt::Display for Num {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Num::Int(v) => write!(f, "{}", v),
+            Num::Float(v) if !v.is_finite() => f.write_str("null"),
+            // `f64`'s display is the shortest string that round-trips, never in exponent form
+            Num::Float(v) if v.fract() == 0.0 => write!(f, "{}.0", v),
+            Num::Float(v) => write!(f, "{}", v),
+        }
+    }
 }
 
-fn parse_json(input: &mut &str) -> PResult<JsonValue> {
-    parse_value(input)
+impl JsonValue {
+    /// Build a `JsonValue::Array` from any iterator of values.
+    pub fn array(iter: impl IntoIterator<Item = JsonValue>) -> JsonValue {
+        JsonValue::Array(iter.into_iter().collect())
+    }
+
+    /// Build a `JsonValue::Object` from any iterator of key/value pairs.
+    pub fn object(iter: impl IntoIterator<Item = (String, JsonValue)>) -> JsonValue {
+        JsonValue::Object(iter.into_iter().collect())
+    }
+
+    /// Return a deep copy with the given keys removed from every object in the tree.
+    pub fn without_keys(&self, keys: &[&str]) -> JsonValue {
+        match self {
+            JsonValue::Array(arr) => {
+                JsonValue::Array(arr.iter().map(|v| v.without_keys(keys)).collect())
+            }
+            JsonValue::Object(obj) => JsonValue::Object(
+                obj.iter()
+                    .filter(|(k, _)| !keys.contains(&k.as_str()))
+                    .map(|(k, v)| (k.clone(), v.without_keys(keys)))
+                    .collect(),
+            ),
+            v => v.clone(),
+        }
+    }
+
+    /// Return a deep copy with `f` applied to every string value, object keys are untouched.
+    pub fn map_strings(&self, f: impl Fn(&str) -> String) -> JsonValue {
+        self.map_strings_with(&f)
+    }
+
+    fn map_strings_with(&self, f: &dyn Fn(&str) -> String) -> JsonValue {
+        match self {
+            JsonValue::String(s) => JsonValue::String(f(s)),
+            JsonValue::Array(arr) => JsonValue::array(arr.iter().map(|v| v.map_strings_with(f))),
+            JsonValue::Object(obj) => JsonValue::Object(
+                obj.iter()
+                    .map(|(k, v)| (k.clone(), v.map_strings_with(f)))
+                    .collect(),
+            ),
+            v => v.clone(),
+        }
+    }
+
+    /// Return a copy keeping `max_depth` levels of nesting, arrays and objects below that
+    /// are replaced by the string `"…"`. Scalars are never replaced.
+    pub fn truncated(&self, max_depth: usize) -> JsonValue {
+        self.truncated_with(max_depth, &JsonValue::String("\u{2026}".to_string()))
+    }
+
+    /// Like [`JsonValue::truncated`], replacing the cut off containers with `marker`.
+    pub fn truncated_with(&self, max_depth: usize, marker: &JsonValue) -> JsonValue {
+        match self {
+            JsonValue::Array(_) | JsonValue::Object(_) if max_depth == 0 => marker.clone(),
+            JsonValue::Array(arr) => {
+                JsonValue::array(arr.iter().map(|v| v.truncated_with(max_depth - 1, marker)))
+            }
+            JsonValue::Object(obj) => JsonValue::Object(
+                obj.iter()
+                    .map(|(k, v)| (k.clone(), v.truncated_with(max_depth - 1, marker)))
+                    .collect(),
+            ),
+            v => v.clone(),
+        }
+    }
+
+    /// Count the nodes in the tree, this one and containers included, for which `pred` holds.
+    pub fn count_matching(&self, pred: impl Fn(&JsonValue) -> bool) -> usize {
+        self.count_matching_with(&pred)
+    }
+
+    fn count_matching_with(&self, pred: &dyn Fn(&JsonValue) -> bool) -> usize {
+        let children = match self {
+            JsonValue::Array(arr) => arr.iter().map(|v| v.count_matching_with(pred)).sum(),
+            JsonValue::Object(obj) => obj.values().map(|v| v.count_matching_with(pred)).sum(),
+            _ => 0,
+        };
+        children + usize::from(pred(self))
+    }
+
+    /// Export the leaves of the tree as environment variables sorted by name. Names join
+    /// `prefix` and the path with `_`, uppercased and with other non-alphanumeric chars
+    /// replaced by `_`, e.g. `APP_ADDRESS_CITY` or `APP_MARKS_0`. Nulls become empty strings.
+    pub fn to_env_vars(&self, prefix: &str) -> Vec<(String, String)> {
+        let mut vars = vec![];
+        self.collect_env_vars(env_var_name(prefix), &mut vars);
+        vars.sort();
+        vars
+    }
+
+    fn collect_env_vars(&self, name: String, vars: &mut Vec<(String, String)>) {
+        let child_name = |key: &str| match name.as_str() {
+            "" => env_var_name(key),
+            _ => format!("{}_{}", name, env_var_name(key)),
+        };
+        match self {
+            JsonValue::Array(arr) => {
+                for (i, v) in arr.iter().enumerate() {
+                    v.collect_env_vars(child_name(&i.to_string()), vars);
+                }
+            }
+            JsonValue::Object(obj) => {
+                for (k, v) in obj {
+                    v.collect_env_vars(child_name(k), vars);
+                }
+            }
+            JsonValue::Null => vars.push((name, String::new())),
+            JsonValue::Bool(b) => vars.push((name, b.to_string())),
+            JsonValue::Number(n) => vars.push((name, n.to_string())),
+            JsonValue::String(s) => vars.push((name, s.clone())),
+        }
+    }
+
+    /// The type of this value.
+    pub fn json_type(&self) -> JsonType {
+        match self {
+            JsonValue::Null => JsonType::Null,
+            JsonValue::Bool(_) => JsonType::Bool,
+            JsonValue::Number(_) => JsonType::Number,
+            JsonValue::String(_) => JsonType::String,
+            JsonValue::Array(_) => JsonType::Array,
+            JsonValue::Object(_) => JsonType::Object,
+        }
+    }
+
+    /// Leniently convert a scalar to `target`, e.g. the string `"42"` to a number or `"yes"`
+    /// to `true`. Values already of the target type are returned unchanged, `None` means the
+    /// value can't be coerced.
+    pub fn coerce_to(&self, target: JsonType) -> Option<JsonValue> {
+        if self.json_type() == target {
+            return Some(self.clone());
+        }
+        let ret = match (self, target) {
+            (JsonValue::String(s), JsonType::Number) => {
+                let s = s.trim();
+                let num = match s.parse::<i64>() {
+                    Ok(v) => Num::Int(v),
+                    Err(_) => Num::Float(s.parse::<f64>().ok().filter(|v| v.is_finite())?),
+                };
+                JsonValue::Number(num)
+            }
+            (JsonValue::String(s), JsonType::Bool) => {
+                match s.trim().to_ascii_lowercase().as_str() {
+                    "true" | "yes" | "on" | "1" => JsonValue::Bool(true),
+                    "false" | "no" | "off" | "0" => JsonValue::Bool(false),
+                    _ => return None,
+                }
+            }
+            (JsonValue::String(s), JsonType::Null) if s.trim() == "null" => JsonValue::Null,
+            (JsonValue::Number(Num::Int(v)), JsonType::Bool) if *v == 0 || *v == 1 => {
+                JsonValue::Bool(*v == 1)
+            }
+            (JsonValue::Number(Num::Int(v)), JsonType::String) => JsonValue::String(v.to_string()),
+            (JsonValue::Number(Num::Float(v)), JsonType::String) => {
+                JsonValue::String(v.to_string())
+            }
+            (JsonValue::Bool(b), JsonType::String) => JsonValue::String(b.to_string()),
+            _ => return None,
+        };
+        Some(ret)
+    }
+
+    /// Read a number, also accepting strings that look numeric such as `"42"` or `"3.5"`.
+    pub fn as_number(&self) -> Option<f64> {
+        match self.coerce_to(JsonType::Number)? {
+            JsonValue::Number(n) => Some(n.as_f64()),
+            _ => None,
+        }
+    }
+
+    /// Decode a string value as standard base64, `None` for other variants or invalid input.
+    pub fn as_base64_bytes(&self) -> Option<Vec<u8>> {
+        match self {
+            JsonValue::String(s) => STANDARD.decode(s).ok(),
+            _ => None,
+        }
+    }
+
+    /// Keep only the object entries for which `pred` holds, other variants are untouched.
+    pub fn retain(&mut self, mut pred: impl FnMut(&str, &JsonValue) -> bool) {
+        if let JsonValue::Object(obj) = self {
+            obj.retain(|k, v| pred(k, v));
+        }
+    }
+
+    /// Keep only the array elements for which `pred` holds, other variants are untouched.
+    pub fn retain_array(&mut self, pred: impl FnMut(&JsonValue) -> bool) {
+        if let JsonValue::Array(arr) = self {
+            arr.retain(pred);
+        }
+    }
+
+    /// Estimate the heap memory held by this tree: string buffers plus the allocations of
+    /// arrays and objects, recursively. Allocator overhead and hash table control bytes are
+    /// not counted, so the result is a lower bound.
+    pub fn deep_size_bytes(&self) -> usize {
+        match self {
+            JsonValue::String(s) => s.capacity(),
+            JsonValue::Array(arr) => {
+                arr.capacity() * std::mem::size_of::<JsonValue>()
+                    + arr.iter().map(JsonValue::deep_size_bytes).sum::<usize>()
+            }
+            JsonValue::Object(obj) => {
+                obj.capacity() * std::mem::size_of::<(String, JsonValue)>()
+                    + obj
+                        .iter()
+                        .map(|(k, v)| k.capacity() + v.deep_size_bytes())
+                        .sum::<usize>()
+            }
+            _ => 0,
+        }
+    }
+
+    /// Consume an object into its `(key, value)` pairs, any other variant yields nothing.
+    pub fn into_object_iter(self) -> std::collections::hash_map::IntoIter<String, JsonValue> {
+        match self {
+            JsonValue::Object(obj) => obj.into_iter(),
+            _ => HashMap::new().into_iter(),
+        }
+    }
 }
 
-fn skip_whitespace<Input, Output, Error, ParseNext>(
-    mut parser: ParseNext,
-) -> impl Parser<Input, (), Error>
-where
-    Input: Stream + StreamIsPartial,
-    <Input as Stream>::Token: AsChar + Clone,
-    Error: ParserError<Input>,
-    ParseNext: Parser<Input, Output, Error>,
-{
-    trace("skip_whitespace", move |input: &mut Input| {
-        let _ = multispace0(input)?;
-        parser.parse_next(input)?;
-        multispace0.parse_next(input)?;
-        Ok(())
+/// Consuming an array yields its elements, objects and scalars yield nothing.
+impl IntoIterator for JsonValue {
+    type Item = JsonValue;
+    type IntoIter = std::vec::IntoIter<JsonValue>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        match self {
+            JsonValue::Array(arr) => arr.into_iter(),
+            _ => Vec::new().into_iter(),
+        }
+    }
+}
+
+impl TryFrom<&JsonValue> for i64 {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &JsonValue) -> Result<Self> {
+        match value {
+            JsonValue::Number(Num::Int(v)) => Ok(*v),
+            _ => Err(type_mismatch::<Self>(value)),
+        }
+    }
+}
+
+/// Integers are widened, possibly losing precision.
+impl TryFrom<&JsonValue> for f64 {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &JsonValue) -> Result<Self> {
+        match value {
+            JsonValue::Number(n) => Ok(n.as_f64()),
+            _ => Err(type_mismatch::<Self>(value)),
+        }
+    }
+}
+
+impl TryFrom<&JsonValue> for bool {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &JsonValue) -> Result<Self> {
+        match value {
+            JsonValue::Bool(b) => Ok(*b),
+            _ => Err(type_mismatch::<Self>(value)),
+        }
+    }
+}
+
+impl TryFrom<&JsonValue> for String {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &JsonValue) -> Result<Self> {
+        match value {
+            JsonValue::String(s) => Ok(s.clone()),
+            _ => Err(type_mismatch::<Self>(value)),
+        }
+    }
+}
+
+fn env_var_name(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            c if c.is_ascii_alphanumeric() => c.to_ascii_uppercase(),
+            _ => '_',
+        })
+        .collect()
+}
+
+fn type_mismatch<T>(value: &JsonValue) -> anyhow::Error {
+    anyhow!(
+        "expected {}, found JSON {:?}",
+        std::any::type_name::<T>(),
+        value.json_type()
+    )
+}
+
+/// Options controlling how strictly JSON input is parsed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ParseOptions {
+    /// Reject `-0` as an integer, `-0.0` remains a valid float.
+    pub reject_negative_zero: bool,
+    /// Accept unquoted identifier keys in objects, e.g. `{ name: "x" }`.
+    pub bare_keys: bool,
+    /// Accept `_` between digits of numbers, e.g. `1_000_000`.
+    pub digit_separators: bool,
+    /// Accept `Infinity`, `-Infinity` and `NaN` as floats.
+    pub non_finite_numbers: bool,
+    /// Read integral floats as integers, e.g. `90.0` as `Int(90)`, see [`Num::normalize`].
+    pub normalize_numbers: bool,
+}
+
+/// Shape of a parsed document, see [`parse_with_metrics`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseMetrics {
+    /// Nesting depth, a scalar document has depth 1 and `[1]` has depth 2.
+    pub max_depth: usize,
+    /// Number of values in the document, containers included.
+    pub node_count: usize,
+    pub string_count: usize,
+    pub number_count: usize,
+}
+
+// state threaded through a single parse
+#[derive(Default)]
+struct ParseContext<'a> {
+    options: ParseOptions,
+    // `None` unless the caller asked for metrics
+    metrics: Option<&'a Cell<ParseMetrics>>,
+    // number of containers enclosing the value being parsed
+    depth: Cell<usize>,
+    // `None` unless the caller asked for comments
+    comments: Option<&'a RefCell<CommentState>>,
+}
+
+#[derive(Default)]
+struct CommentState {
+    // comments seen since the last value started, keyed by the remaining input length
+    // because backtracking can scan the same comment twice
+    pending: Vec<(usize, String)>,
+    // reference tokens of the value being parsed
+    path: Vec<String>,
+    comments: HashMap<String, Vec<String>>,
+}
+
+/// Parse a JSON document with the winnow backend, the whole input must be consumed.
+pub fn winnow_parse(input: &str) -> Result<JsonValue> {
+    parse_document(input, &ParseContext::default())
+}
+
+fn parse_document(input: &str, ctx: &ParseContext) -> Result<JsonValue> {
+    delimited(
+        |i: &mut &str| parse_ws(i, ctx),
+        |i: &mut &str| parse_value_with(i, ctx),
+        |i: &mut &str| parse_ws(i, ctx),
+    )
+    .parse(input)
+    .map_err(|e| {
+        let (line, column) = line_column(input, e.offset());
+        anyhow!(
+            "Failed to parse JSON at line {}, column {} near {:?}",
+            line,
+            column,
+            snippet(input, e.offset(), 10)
+        )
     })
 }
 
+pub fn parse_json(input: &mut &str) -> PResult<JsonValue> {
+    parse_value(input)
+}
+
+/// Parse the JSON value at the start of `input`, returning it with the number of bytes it
+/// spans. Anything after the value is left unparsed.
+pub fn parse_value_partial(input: &str) -> PResult<(JsonValue, usize)> {
+    let rest = &mut (&*input);
+    let value = parse_value(rest)?;
+    // containers also consume the whitespace after their closing bracket
+    let consumed = &input[..input.len() - rest.len()];
+    Ok((value, consumed.trim_end().len()))
+}
+
+/// Find the first JSON object or array embedded in `s`, e.g. in a log line, returning its
+/// byte span and parsed value. Brackets that don't start a valid value are skipped.
+pub fn find_json(s: &str) -> Option<(Range<usize>, JsonValue)> {
+    s.char_indices()
+        .filter(|(_, c)| matches!(c, '{' | '['))
+        .find_map(|(start, _)| {
+            let (value, len) = parse_value_partial(&s[start..]).ok()?;
+            Some((start..start + len, value))
+        })
+}
+
+/// Parse a JSON document using the given options.
+pub fn parse_json_with(input: &mut &str, options: &ParseOptions) -> PResult<JsonValue> {
+    let ctx = ParseContext {
+        options: *options,
+        ..Default::default()
+    };
+    parse_value_with(input, &ctx)
+}
+
+/// Parse a whole JSON document like [`winnow_parse`], also collecting [`ParseMetrics`].
+pub fn parse_with_metrics(input: &str) -> Result<(JsonValue, ParseMetrics)> {
+    let metrics = Cell::new(ParseMetrics::default());
+    let ctx = ParseContext {
+        metrics: Some(&metrics),
+        ..Default::default()
+    };
+    let value = parse_document(input, &ctx)?;
+    Ok((value, metrics.get()))
+}
+
+/// Parse a whole JSON document that may contain `//` and `/* */` comments.
+///
+/// Each comment is attached to the value following it and returned keyed by that value's
+/// JSON Pointer. Comments with no value after them, e.g. before a closing `}`, are dropped.
+pub fn parse_with_comments(input: &str) -> Result<(JsonValue, HashMap<String, Vec<String>>)> {
+    let state = RefCell::new(CommentState::default());
+    let ctx = ParseContext {
+        comments: Some(&state),
+        ..Default::default()
+    };
+    let value = parse_document(input, &ctx)?;
+    Ok((value, state.into_inner().comments))
+}
+
+/// Parse a JavaScript-object-literal style document, which allows unquoted object keys.
+pub fn parse_json5(input: &mut &str) -> PResult<JsonValue> {
+    let options = ParseOptions {
+        bare_keys: true,
+        ..Default::default()
+    };
+    parse_json_with(input, &options)
+}
+
+// whitespace, plus comments when they are being collected
+fn parse_ws(input: &mut &str, ctx: &ParseContext) -> PResult<()> {
+    multispace0(input)?;
+    let Some(state) = ctx.comments else {
+        return Ok(());
+    };
+    loop {
+        let offset = input.len();
+        let comment = opt(alt((
+            preceded("//", take_till(0.., '\n')),
+            delimited("/*", take_until(0.., "*/"), "*/"),
+        )))
+        .parse_next(input)?;
+        let Some(comment) = comment else {
+            return Ok(());
+        };
+        let pending = &mut state.borrow_mut().pending;
+        if !pending.iter().any(|(o, _)| *o == offset) {
+            pending.push((offset, comment.trim().to_string()));
+        }
+        multispace0(input)?;
+    }
+}
+
+// a punctuation char with optional whitespace around it
+fn parse_sep(input: &mut &str, c: char, ctx: &ParseContext) -> PResult<()> {
+    parse_ws(input, ctx)?;
+    c.void().parse_next(input)?;
+    // comments before a closing bracket have no value to attach to
+    if let (']' | '}', Some(state)) = (c, ctx.comments) {
+        state.borrow_mut().pending.clear();
+    }
+    parse_ws(input, ctx)
+}
+
 fn parse_null(input: &mut &str) -> PResult<()> {
     "null".value(()).parse_next(input)
 }
@@ -79,60 +600,196 @@ fn parse_bool(input: &mut &str) -> PResult<bool> {
     alt(("true", "false")).parse_to().parse_next(input)
 }
 
+pub fn parse_num(input: &mut &str) -> PResult<Num> {
+    parse_num_with(input, &ParseOptions::default())
+}
+
 // FIXME: num parse doesn't work with scientific notation, fix it
-fn parse_num(input: &mut &str) -> PResult<Num> {
+pub fn parse_num_with(input: &mut &str, options: &ParseOptions) -> PResult<Num> {
     // process the sign
     let sign = opt("-").map(|s| s.is_some()).parse_next(input)?;
-    let num = digit1.parse_to::<i64>().parse_next(input)?;
+    if options.non_finite_numbers {
+        if opt("Infinity").parse_next(input)?.is_some() {
+            let v = if sign {
+                f64::NEG_INFINITY
+            } else {
+                f64::INFINITY
+            };
+            return Ok(Num::Float(v));
+        }
+        if !sign && opt("NaN").parse_next(input)?.is_some() {
+            return Ok(Num::Float(f64::NAN));
+        }
+    }
+    let num = parse_digits(input, options)?;
     let ret: Result<(), ErrMode<ContextError>> = ".".value(()).parse_next(input);
+    // the sign goes into the literal so `-0.0` keeps its sign bit
+    let sign = if sign { "-" } else { "" };
     if ret.is_ok() {
-        let frac = digit1.parse_to::<i64>().parse_next(input)?;
-        let v = format!("{}.{}", num, frac).parse::<f64>().unwrap();
-        Ok(if sign {
-            Num::Float(-v as _)
-        } else {
-            Num::Float(v as _)
-        })
+        let frac = parse_digits(input, options)?;
+        let v = format!("{}{}.{}", sign, num, frac)
+            .parse::<f64>()
+            .map_err(|_| ErrMode::from_error_kind(input, ErrorKind::Verify))?;
+        if options.normalize_numbers {
+            return Ok(Num::Float(v).normalize());
+        }
+        Ok(Num::Float(v))
+    } else {
+        let v = format!("{}{}", sign, num)
+            .parse::<i64>()
+            .map_err(|_| ErrMode::from_error_kind(input, ErrorKind::Verify))?;
+        if !sign.is_empty() && v == 0 && options.reject_negative_zero {
+            return Err(ErrMode::from_error_kind(input, ErrorKind::Verify));
+        }
+        Ok(Num::Int(v))
+    }
+}
+
+// the digits are returned as text so a fraction keeps its leading zeros
+fn parse_digits<'a>(input: &mut &'a str, options: &ParseOptions) -> PResult<Cow<'a, str>> {
+    if options.digit_separators {
+        // separators are only allowed between digits, never leading, trailing or doubled
+        separated(1.., digit1, '_')
+            .map(|()| ())
+            .take()
+            .map(|digits: &str| Cow::Owned(digits.replace('_', "")))
+            .parse_next(input)
     } else {
-        Ok(if sign { Num::Int(-num) } else { Num::Int(num) })
+        digit1.map(Cow::Borrowed).parse_next(input)
     }
 }
 
-// json allows quoted strings to have escaped characters, so we need to handle that, but we won't do that here
 fn parse_string(input: &mut &str) -> PResult<String> {
-    let ret = delimited('"', take_until(0.., '"'), '"').parse_next(input)?;
-    Ok(ret.to_string())
+    quoted('"', true).parse_next(input)
 }
 
-fn parse_array(input: &mut &str) -> PResult<Vec<JsonValue>> {
-    let sep1 = skip_whitespace('[');
-    let sep2 = skip_whitespace(']');
-    let sep_comma = skip_whitespace(',');
-    let parse_values = separated(1.., parse_value, sep_comma);
-    delimited(sep1, parse_values, sep2).parse_next(input)
+fn parse_identifier(input: &mut &str) -> PResult<String> {
+    (
+        one_of(|c: char| c.is_ascii_alphabetic() || c == '_' || c == '$'),
+        take_while(0.., |c: char| {
+            c.is_ascii_alphanumeric() || c == '_' || c == '$'
+        }),
+    )
+        .take()
+        .map(|s: &str| s.to_string())
+        .parse_next(input)
 }
 
-fn parse_object(input: &mut &str) -> PResult<HashMap<String, JsonValue>> {
-    let sep1 = skip_whitespace('{');
-    let sep2 = skip_whitespace('}');
-    let sep_comma = skip_whitespace(',');
-    let sep_colon = skip_whitespace(':');
-    let parse_kv_pair = separated_pair(parse_string, sep_colon, parse_value);
-    let parse_kv = separated(1.., parse_kv_pair, sep_comma);
-    delimited(sep1, parse_kv, sep2).parse_next(input)
+pub fn parse_array(input: &mut &str) -> PResult<Vec<JsonValue>> {
+    parse_array_with(input, &ParseContext::default())
 }
 
-fn parse_value(input: &mut &str) -> PResult<JsonValue> {
-    alt((
+fn parse_array_with(input: &mut &str, ctx: &ParseContext) -> PResult<Vec<JsonValue>> {
+    let sep1 = |i: &mut &str| parse_sep(i, '[', ctx);
+    let sep2 = |i: &mut &str| parse_sep(i, ']', ctx);
+    let sep_comma = |i: &mut &str| parse_sep(i, ',', ctx);
+    let index = Cell::new(0);
+    let parse_item = move |i: &mut &str| {
+        let n = index.get();
+        index.set(n + 1);
+        parse_value_at(i, ctx, || n.to_string())
+    };
+    let parse_values = separated(1.., cut_err(parse_item), sep_comma);
+    // once the opening bracket matched there's no point in trying other alternatives
+    preceded(sep1, cut_err(terminated(parse_values, sep2))).parse_next(input)
+}
+
+pub fn parse_object(input: &mut &str) -> PResult<HashMap<String, JsonValue>> {
+    parse_object_with(input, &ParseContext::default())
+}
+
+fn parse_object_with(input: &mut &str, ctx: &ParseContext) -> PResult<HashMap<String, JsonValue>> {
+    let pairs = parse_object_pairs_with(input, ctx)?;
+    Ok(pairs.into_iter().collect())
+}
+
+/// Parse an object into its key/value pairs in source order, duplicate keys included.
+/// Nested objects are still collected into maps.
+pub fn parse_object_pairs(input: &mut &str) -> PResult<Vec<(String, JsonValue)>> {
+    parse_object_pairs_with(input, &ParseContext::default())
+}
+
+fn parse_object_pairs_with(
+    input: &mut &str,
+    ctx: &ParseContext,
+) -> PResult<Vec<(String, JsonValue)>> {
+    let sep1 = |i: &mut &str| parse_sep(i, '{', ctx);
+    let sep2 = |i: &mut &str| parse_sep(i, '}', ctx);
+    let sep_comma = |i: &mut &str| parse_sep(i, ',', ctx);
+    let parse_key = |i: &mut &str| {
+        if ctx.options.bare_keys {
+            alt((parse_string, parse_identifier)).parse_next(i)
+        } else {
+            parse_string(i)
+        }
+    };
+    let parse_kv_pair = move |i: &mut &str| -> PResult<(String, JsonValue)> {
+        let key = parse_key(i)?;
+        parse_sep(i, ':', ctx)?;
+        let value = parse_value_at(i, ctx, || key.clone())?;
+        Ok((key, value))
+    };
+    let parse_kv = separated(1.., cut_err(parse_kv_pair), sep_comma);
+    preceded(sep1, cut_err(terminated(parse_kv, sep2))).parse_next(input)
+}
+
+pub fn parse_value(input: &mut &str) -> PResult<JsonValue> {
+    parse_value_with(input, &ParseContext::default())
+}
+
+// parse a container element, `token` is only evaluated when comments are collected
+fn parse_value_at(
+    input: &mut &str,
+    ctx: &ParseContext,
+    token: impl FnOnce() -> String,
+) -> PResult<JsonValue> {
+    let Some(state) = ctx.comments else {
+        return parse_value_with(input, ctx);
+    };
+    state.borrow_mut().path.push(token());
+    let ret = parse_value_with(input, ctx);
+    state.borrow_mut().path.pop();
+    ret
+}
+
+fn parse_value_with(input: &mut &str, ctx: &ParseContext) -> PResult<JsonValue> {
+    #[cfg(test)]
+    PARSED_VALUES.with(|c| c.set(c.get() + 1));
+    if let Some(state) = ctx.comments {
+        let state = &mut *state.borrow_mut();
+        if !state.pending.is_empty() {
+            let path = pointer::to_pointer(&state.path);
+            let comments = state.comments.entry(path).or_default();
+            comments.extend(state.pending.drain(..).map(|(_, c)| c));
+        }
+    }
+    let depth = ctx.depth.get();
+    ctx.depth.set(depth + 1);
+    let ret = alt((
         parse_null.value(JsonValue::Null),
         parse_bool.map(JsonValue::Bool),
-        parse_num.map(JsonValue::Number),
+        (|i: &mut &str| parse_num_with(i, &ctx.options)).map(JsonValue::Number),
         // parse_number.map(JsonValue::Number),
         parse_string.map(JsonValue::String),
-        parse_array.map(JsonValue::Array),
-        parse_object.map(JsonValue::Object),
+        (|i: &mut &str| parse_array_with(i, ctx)).map(JsonValue::Array),
+        (|i: &mut &str| parse_object_with(i, ctx)).map(JsonValue::Object),
     ))
-    .parse_next(input)
+    .parse_next(input);
+    ctx.depth.set(depth);
+    let value = ret?;
+
+    if let Some(metrics) = ctx.metrics {
+        let mut m = metrics.get();
+        m.max_depth = m.max_depth.max(depth + 1);
+        m.node_count += 1;
+        match value {
+            JsonValue::String(_) => m.string_count += 1,
+            JsonValue::Number(_) => m.number_count += 1,
+            _ => {}
+        }
+        metrics.set(m);
+    }
+    Ok(value)
 }
 
 #[cfg(test)]
@@ -241,4 +898,753 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_json_value_builders() -> PResult<(), ContextError> {
+        let marks = JsonValue::array([
+            JsonValue::Number(Num::Float(87.0)),
+            JsonValue::Number(Num::Int(90)),
+            JsonValue::Number(Num::Float(-45.7)),
+            JsonValue::Number(Num::Float(67.9)),
+        ]);
+        let s = "[87.0, 90, -45.7, 67.9]";
+        let input = &mut (&*s);
+        assert_eq!(marks, parse_value(input)?);
+
+        let address = JsonValue::object([
+            (
+                "city".to_string(),
+                JsonValue::String("New York".to_string()),
+            ),
+            ("zip".to_string(), JsonValue::Number(Num::Int(10001))),
+        ]);
+        let s = r#"{"city": "New York", "zip": 10001}"#;
+        let input = &mut (&*s);
+        assert_eq!(address, parse_value(input)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_num_negative_zero() -> PResult<(), ContextError> {
+        let s = "-0";
+        let input = &mut (&*s);
+        let result = parse_num(input)?;
+        assert_eq!(result, Num::Int(0));
+
+        let strict = ParseOptions {
+            reject_negative_zero: true,
+            ..Default::default()
+        };
+        let s = "-0";
+        let input = &mut (&*s);
+        assert!(parse_num_with(input, &strict).is_err());
+
+        let s = "-0.0";
+        let input = &mut (&*s);
+        let result = parse_num_with(input, &strict)?;
+        assert!(matches!(result, Num::Float(v) if v == 0.0 && v.is_sign_negative()));
+
+        let s = "[1, -0]";
+        let input = &mut (&*s);
+        assert!(parse_json_with(input, &strict).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_string_with_escapes() -> PResult<(), ContextError> {
+        let s = r#""John \"JD\" Doe\tNew York\u0021""#;
+        let input = &mut (&*s);
+        let result = parse_string(input)?;
+        assert_eq!(result, "John \"JD\" Doe\tNew York!".to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_without_keys() -> PResult<(), ContextError> {
+        let s = r#"{
+            "name": "John Doe",
+            "password": "secret",
+            "sessions": [{"id": 1, "token": "abc"}],
+            "account": {"login": "jd", "password": "hunter2"}
+        }"#;
+        let input = &mut (&*s);
+        let result = parse_json(input)?.without_keys(&["password", "token"]);
+
+        let s = r#"{
+            "name": "John Doe",
+            "sessions": [{"id": 1}],
+            "account": {"login": "jd"}
+        }"#;
+        let input = &mut (&*s);
+        assert_eq!(result, parse_json(input)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_bare_keys() -> PResult<(), ContextError> {
+        let s = r#"{ name: "John Doe", "age": 43, $is_student: false }"#;
+        let input = &mut (&*s);
+        let result = parse_json5(input)?;
+        let expected = JsonValue::object([
+            (
+                "name".to_string(),
+                JsonValue::String("John Doe".to_string()),
+            ),
+            ("age".to_string(), JsonValue::Number(Num::Int(43))),
+            ("$is_student".to_string(), JsonValue::Bool(false)),
+        ]);
+        assert_eq!(result, expected);
+
+        let input = &mut (&*s);
+        assert!(parse_json(input).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_winnow_parse_error_position() {
+        let s = "{\n  \"name\": \"John Doe\",\n  \"marks\": [87.0, @]\n}";
+        let err = winnow_parse(s).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Failed to parse JSON at line 3, column 19"));
+
+        let err = winnow_parse(r#"{"a": 1} trailing"#).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Failed to parse JSON at line 1, column 10"));
+    }
+
+    #[test]
+    fn test_num_as_f64() {
+        assert_eq!(Num::Int(3).as_f64(), 3.0);
+        assert_eq!(Num::Float(-2.5).as_f64(), -2.5);
+    }
+
+    #[test]
+    fn test_num_is_integer() {
+        assert!(Num::Int(-7).is_integer());
+        assert!(Num::Float(3.0).is_integer());
+        assert!(!Num::Float(3.5).is_integer());
+        assert!(!Num::Float(f64::INFINITY).is_integer());
+        assert!(!Num::Float(f64::NAN).is_integer());
+    }
+
+    #[test]
+    fn test_num_normalize() {
+        assert_eq!(Num::Float(3.0).normalize(), Num::Int(3));
+        assert_eq!(Num::Float(-42.0).normalize(), Num::Int(-42));
+        assert_eq!(Num::Int(5).normalize(), Num::Int(5));
+        assert_eq!(Num::Float(3.25).normalize(), Num::Float(3.25));
+        assert_eq!(Num::Float(1e300).normalize(), Num::Float(1e300));
+        assert!(matches!(Num::Float(-0.0).normalize(), Num::Float(v) if v.is_sign_negative()));
+    }
+
+    #[test]
+    fn test_into_iter_array() -> Result<()> {
+        let value = winnow_parse(r#"["a", 1, null]"#)?;
+        let items: Vec<JsonValue> = value.into_iter().collect();
+        assert_eq!(
+            items,
+            vec![
+                JsonValue::String("a".to_string()),
+                JsonValue::Number(Num::Int(1)),
+                JsonValue::Null,
+            ]
+        );
+
+        assert_eq!(JsonValue::Bool(true).into_iter().count(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_into_object_iter() -> Result<()> {
+        let value = winnow_parse(r#"{"b": 2, "a": 1}"#)?;
+        let mut pairs: Vec<(String, JsonValue)> = value.into_object_iter().collect();
+        pairs.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            pairs,
+            vec![
+                ("a".to_string(), JsonValue::Number(Num::Int(1))),
+                ("b".to_string(), JsonValue::Number(Num::Int(2))),
+            ]
+        );
+
+        assert_eq!(JsonValue::array([]).into_object_iter().count(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_coerce_to_number() {
+        let s = JsonValue::String("42".to_string());
+        assert_eq!(
+            s.coerce_to(JsonType::Number),
+            Some(JsonValue::Number(Num::Int(42)))
+        );
+        let s = JsonValue::String(" -1.5 ".to_string());
+        assert_eq!(
+            s.coerce_to(JsonType::Number),
+            Some(JsonValue::Number(Num::Float(-1.5)))
+        );
+        assert_eq!(
+            JsonValue::String("abc".to_string()).coerce_to(JsonType::Number),
+            None
+        );
+        assert_eq!(
+            JsonValue::String("NaN".to_string()).coerce_to(JsonType::Number),
+            None
+        );
+
+        let n = JsonValue::Number(Num::Int(7));
+        assert_eq!(n.coerce_to(JsonType::Number), Some(n.clone()));
+        assert_eq!(
+            n.coerce_to(JsonType::String),
+            Some(JsonValue::String("7".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_coerce_to_bool() {
+        for s in ["true", "yes", "YES", "on"] {
+            let v = JsonValue::String(s.to_string());
+            assert_eq!(v.coerce_to(JsonType::Bool), Some(JsonValue::Bool(true)));
+        }
+        for s in ["false", "no", "Off"] {
+            let v = JsonValue::String(s.to_string());
+            assert_eq!(v.coerce_to(JsonType::Bool), Some(JsonValue::Bool(false)));
+        }
+        assert_eq!(
+            JsonValue::String("maybe".to_string()).coerce_to(JsonType::Bool),
+            None
+        );
+        assert_eq!(JsonValue::array([]).coerce_to(JsonType::Bool), None);
+    }
+
+    #[test]
+    fn test_winnow_parse_error_snippet() {
+        let err = winnow_parse(r#"{"a": @}"#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"Failed to parse JSON at line 1, column 7 near "{\"a\": @}""#
+        );
+    }
+
+    #[test]
+    fn test_parse_num_non_finite() -> PResult<(), ContextError> {
+        let lenient = ParseOptions {
+            non_finite_numbers: true,
+            ..Default::default()
+        };
+        let input = &mut "Infinity";
+        assert_eq!(parse_num_with(input, &lenient)?, Num::Float(f64::INFINITY));
+        let input = &mut "-Infinity";
+        assert_eq!(
+            parse_num_with(input, &lenient)?,
+            Num::Float(f64::NEG_INFINITY)
+        );
+        let input = &mut "NaN";
+        assert!(matches!(parse_num_with(input, &lenient)?, Num::Float(v) if v.is_nan()));
+        assert!(parse_num_with(&mut "-NaN", &lenient).is_err());
+        assert_eq!(parse_num_with(&mut "-1.5", &lenient)?, Num::Float(-1.5));
+
+        for s in ["Infinity", "-Infinity", "NaN"] {
+            assert!(parse_num(&mut &*s).is_err());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_normalize_numbers() -> PResult<(), ContextError> {
+        let options = ParseOptions {
+            normalize_numbers: true,
+            ..Default::default()
+        };
+        let input = &mut "[87.0, 90, -45.7, -0.0]";
+        let value = parse_json_with(input, &options)?;
+        assert_eq!(
+            value,
+            JsonValue::array([
+                JsonValue::Number(Num::Int(87)),
+                JsonValue::Number(Num::Int(90)),
+                JsonValue::Number(Num::Float(-45.7)),
+                JsonValue::Number(Num::Float(-0.0)),
+            ])
+        );
+        let input = &mut "[87.0]";
+        let value = parse_json_with(input, &ParseOptions::default())?;
+        assert_eq!(
+            value,
+            JsonValue::array([JsonValue::Number(Num::Float(87.0))])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_num_digit_separators() -> PResult<(), ContextError> {
+        let lenient = ParseOptions {
+            digit_separators: true,
+            ..Default::default()
+        };
+        let s = "1_000_000";
+        let input = &mut (&*s);
+        assert_eq!(parse_num_with(input, &lenient)?, Num::Int(1_000_000));
+
+        let s = "-12.345_678";
+        let input = &mut (&*s);
+        assert_eq!(parse_num_with(input, &lenient)?, Num::Float(-12.345678));
+
+        for s in ["1__0", "1_", "_1"] {
+            let input = &mut (&*s);
+            assert!(terminated(
+                |i: &mut &str| parse_num_with(i, &lenient),
+                winnow::combinator::eof
+            )
+            .parse_next(input)
+            .is_err());
+        }
+
+        let s = "1_000";
+        let input = &mut (&*s);
+        assert!(terminated(parse_num, winnow::combinator::eof)
+            .parse_next(input)
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_as_base64_bytes() {
+        let value = JsonValue::String("aGVsbG8gd29ybGQ=".to_string());
+        assert_eq!(value.as_base64_bytes(), Some(b"hello world".to_vec()));
+
+        assert_eq!(JsonValue::Number(Num::Int(42)).as_base64_bytes(), None);
+        assert_eq!(
+            JsonValue::String("not base64!".to_string()).as_base64_bytes(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_retain() -> Result<()> {
+        let mut value = winnow_parse(r#"{"name": "John", "age": 43, "score": 9.5, "ok": true}"#)?;
+        value.retain(|_, v| !matches!(v, JsonValue::Number(_)));
+        assert_eq!(value, winnow_parse(r#"{"name": "John", "ok": true}"#)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_retain_array() -> Result<()> {
+        let mut value = winnow_parse(r#"[1, null, "a", null]"#)?;
+        value.retain_array(|v| *v != JsonValue::Null);
+        assert_eq!(value, winnow_parse(r#"[1, "a"]"#)?);
+
+        let mut value = JsonValue::Null;
+        value.retain_array(|_| false);
+        assert_eq!(value, JsonValue::Null);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_with_metrics() -> Result<()> {
+        let s = r#"{
+            "name": "John Doe",
+            "age": 43,
+            "is_student": false,
+            "marks": [87.0, 90, -45.7, 67.9],
+            "address": {
+                "city": "New York",
+                "zip": 10001
+            }
+        }"#;
+        let (value, metrics) = parse_with_metrics(s)?;
+        assert_eq!(value, winnow_parse(s)?);
+        assert_eq!(
+            metrics,
+            ParseMetrics {
+                max_depth: 3,
+                node_count: 12,
+                string_count: 2,
+                number_count: 6,
+            }
+        );
+
+        let (_, metrics) = parse_with_metrics("true")?;
+        assert_eq!(metrics.max_depth, 1);
+        assert_eq!(metrics.node_count, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_as_number() {
+        assert_eq!(JsonValue::Number(Num::Float(2.5)).as_number(), Some(2.5));
+        assert_eq!(JsonValue::Number(Num::Int(-7)).as_number(), Some(-7.0));
+        assert_eq!(JsonValue::String("42".to_string()).as_number(), Some(42.0));
+        assert_eq!(
+            JsonValue::String("3.25".to_string()).as_number(),
+            Some(3.25)
+        );
+        assert_eq!(JsonValue::String("forty".to_string()).as_number(), None);
+        assert_eq!(JsonValue::Bool(true).as_number(), None);
+    }
+
+    #[test]
+    fn test_parse_with_comments() -> Result<()> {
+        let s = r#"// the whole config
+        {
+            // listen port
+            "port": 8080,
+            /* upstream servers,
+               tried in order */
+            "servers": [
+                "a.example.com",
+                // fallback
+                "b.example.com"
+            ],
+            // spans a/b
+            "a/b": { /* nested */ "x": true } // dropped
+        }"#;
+        let (value, comments) = parse_with_comments(s)?;
+        let plain = r#"{
+            "port": 8080,
+            "servers": ["a.example.com", "b.example.com"],
+            "a/b": {"x": true}
+        }"#;
+        assert_eq!(value, winnow_parse(plain)?);
+        let expected: HashMap<String, Vec<String>> = [
+            ("", vec!["the whole config"]),
+            ("/port", vec!["listen port"]),
+            (
+                "/servers",
+                vec!["upstream servers,\n               tried in order"],
+            ),
+            ("/servers/1", vec!["fallback"]),
+            ("/a~1b", vec!["spans a/b"]),
+            ("/a~1b/x", vec!["nested"]),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.into_iter().map(String::from).collect()))
+        .collect();
+        assert_eq!(comments, expected);
+
+        // comments are still rejected by the strict parser
+        assert!(winnow_parse(s).is_err());
+
+        let (_, comments) = parse_with_comments(
+            r#"{"a": [1 /* c */], "b": {"x": 2 // d
+        }, "c": 3}"#,
+        )?;
+        assert!(comments.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_from_json_value() -> Result<()> {
+        let n: i64 = (&JsonValue::Number(Num::Int(42))).try_into()?;
+        assert_eq!(n, 42);
+        let f: f64 = (&JsonValue::Number(Num::Float(2.5))).try_into()?;
+        assert_eq!(f, 2.5);
+        let f: f64 = (&JsonValue::Number(Num::Int(3))).try_into()?;
+        assert_eq!(f, 3.0);
+        let b: bool = (&JsonValue::Bool(true)).try_into()?;
+        assert!(b);
+        let s: String = (&JsonValue::String("hi".to_string())).try_into()?;
+        assert_eq!(s, "hi");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_from_json_value_mismatch() {
+        let ret: Result<i64> = (&JsonValue::String("42".to_string())).try_into();
+        assert_eq!(
+            ret.unwrap_err().to_string(),
+            "expected i64, found JSON String"
+        );
+        let ret: Result<i64> = (&JsonValue::Number(Num::Float(1.5))).try_into();
+        assert_eq!(
+            ret.unwrap_err().to_string(),
+            "expected i64, found JSON Number"
+        );
+        let ret: Result<bool> = (&JsonValue::Null).try_into();
+        assert_eq!(
+            ret.unwrap_err().to_string(),
+            "expected bool, found JSON Null"
+        );
+    }
+
+    #[test]
+    fn test_parse_num_negative_zero_float() -> PResult<(), ContextError> {
+        let s = "-0.0";
+        let input = &mut (&*s);
+        let result = parse_num(input)?;
+        // `-0.0 == 0.0`, so check the sign bit explicitly
+        assert!(matches!(result, Num::Float(v) if v == 0.0 && v.is_sign_negative()));
+
+        let s = "0.0";
+        let input = &mut (&*s);
+        let result = parse_num(input)?;
+        assert!(matches!(result, Num::Float(v) if v == 0.0 && v.is_sign_positive()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_map_strings() -> Result<()> {
+        let value = winnow_parse(
+            r#"{"name": "John Doe", "age": 43, "is_student": false, "tags": ["a", null, ["b"]]}"#,
+        )?;
+        let expected = winnow_parse(
+            r#"{"name": "JOHN DOE", "age": 43, "is_student": false, "tags": ["A", null, ["B"]]}"#,
+        )?;
+        assert_eq!(value.map_strings(str::to_uppercase), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_num_never_panics() {
+        let seeds = [
+            "0.05".to_string(),
+            "-1.000001".to_string(),
+            "9223372036854775807".to_string(),
+            "-9223372036854775808".to_string(),
+            "9223372036854775808".to_string(),
+            format!("1.{}", "9".repeat(400)),
+            format!("{}.5", "9".repeat(400)),
+            "1.".to_string(),
+            "-".to_string(),
+            "--1".to_string(),
+            "1__0".to_string(),
+        ];
+        let alphabet = ['-', '0', '1', '9', '.', '_', 'e', '+', ' '];
+        let generated = (0..alphabet.len().pow(4)).map(|mut n| {
+            (0..4)
+                .map(|_| {
+                    let c = alphabet[n % alphabet.len()];
+                    n /= alphabet.len();
+                    c
+                })
+                .collect::<String>()
+        });
+        let options = ParseOptions {
+            reject_negative_zero: true,
+            digit_separators: true,
+            ..Default::default()
+        };
+        for s in seeds.into_iter().chain(generated) {
+            let _ = parse_num(&mut s.as_str());
+            let _ = parse_num_with(&mut s.as_str(), &options);
+        }
+    }
+
+    #[test]
+    fn test_parse_num_fraction_leading_zeros() -> PResult<(), ContextError> {
+        assert_eq!(parse_num(&mut "1.05")?, Num::Float(1.05));
+        assert_eq!(parse_num(&mut "-0.001")?, Num::Float(-0.001));
+        assert_eq!(parse_num(&mut "-9223372036854775808")?, Num::Int(i64::MIN));
+        assert!(parse_num(&mut "9223372036854775808").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_deep_size_bytes() -> Result<()> {
+        assert_eq!(JsonValue::Null.deep_size_bytes(), 0);
+        assert_eq!(JsonValue::Number(Num::Int(1)).deep_size_bytes(), 0);
+
+        let small = winnow_parse(r#"{"a": 1}"#)?;
+        let items: Vec<String> = (0..100)
+            .map(|i| format!(r#"{{"id": {}, "name": "item number {}"}}"#, i, i))
+            .collect();
+        let large = winnow_parse(&format!(r#"{{"items": [{}]}}"#, items.join(",")))?;
+        assert!(small.deep_size_bytes() > 0);
+        assert!(large.deep_size_bytes() > 100 * small.deep_size_bytes());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_value_partial() -> PResult<(), ContextError> {
+        let (value, len) = parse_value_partial(r#"{"status": 200} trailing"#)?;
+        assert_eq!(
+            value.pointer("/status"),
+            Some(&JsonValue::Number(Num::Int(200)))
+        );
+        assert_eq!(len, 15);
+        assert!(parse_value_partial("trailing").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_json() {
+        let line = r#"2024-01-01 INFO request handled: {"status":200,"path":"/api"}"#;
+        let (span, value) = find_json(line).unwrap();
+        assert_eq!(&line[span.clone()], r#"{"status":200,"path":"/api"}"#);
+        assert_eq!(span.end, line.len());
+        assert_eq!(
+            value.pointer("/path"),
+            Some(&JsonValue::String("/api".to_string()))
+        );
+
+        let line = r#"WARN retry {attempt 2} [worker-1] payload=[1, 2] {"ok": false}"#;
+        let (span, value) = find_json(line).unwrap();
+        assert_eq!(&line[span], "[1, 2]");
+        assert_eq!(
+            value,
+            JsonValue::array([
+                JsonValue::Number(Num::Int(1)),
+                JsonValue::Number(Num::Int(2))
+            ])
+        );
+
+        assert!(find_json("no json {here}").is_none());
+    }
+
+    #[test]
+    fn test_truncated() -> Result<()> {
+        let value = winnow_parse(
+            r#"{"name": "John Doe", "age": 43, "marks": [87.0, 90], "address": {"city": "New York", "zip": 10001}}"#,
+        )?;
+        let marker = JsonValue::String("\u{2026}".to_string());
+        let ret = value.truncated(1);
+        assert_eq!(ret.pointer("/address"), Some(&marker));
+        assert_eq!(ret.pointer("/marks"), Some(&marker));
+        assert_eq!(ret.pointer("/age"), Some(&JsonValue::Number(Num::Int(43))));
+
+        assert_eq!(value.truncated(2), value);
+        assert_eq!(value.truncated(0), marker);
+        assert_eq!(
+            value
+                .truncated_with(1, &JsonValue::Null)
+                .pointer("/address"),
+            Some(&JsonValue::Null)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_num_display() -> PResult<(), ContextError> {
+        assert_eq!(Num::Int(42).to_string(), "42");
+        assert_eq!(Num::Int(-7).to_string(), "-7");
+        assert_eq!(Num::Float(1.5).to_string(), "1.5");
+        assert_eq!(Num::Float(3.0).to_string(), "3.0");
+        assert_eq!(Num::Float(-0.0).to_string(), "-0.0");
+        assert_eq!(Num::Float(f64::NAN).to_string(), "null");
+        assert_eq!(Num::Float(f64::NEG_INFINITY).to_string(), "null");
+
+        for n in [
+            Num::Int(42),
+            Num::Int(i64::MIN),
+            Num::Float(1.5),
+            Num::Float(3.0),
+            Num::Float(0.1 + 0.2),
+            Num::Float(-1.000001),
+            Num::Float(1e-7),
+            Num::Float(1e300),
+        ] {
+            let s = n.to_string();
+            let parsed = parse_num(&mut s.as_str())?;
+            assert_eq!(parsed, n, "{}", s);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_object_pairs() -> PResult<(), ContextError> {
+        let s = r#"{"b": true, "a": 1, "a": 2}"#;
+        let input = &mut (&*s);
+        let pairs = parse_object_pairs(input)?;
+        assert_eq!(
+            pairs,
+            vec![
+                ("b".to_string(), JsonValue::Bool(true)),
+                ("a".to_string(), JsonValue::Number(Num::Int(1))),
+                ("a".to_string(), JsonValue::Number(Num::Int(2))),
+            ]
+        );
+
+        // the map keeps the last duplicate
+        let input = &mut (&*s);
+        let obj = parse_object(input)?;
+        assert_eq!(obj.len(), 2);
+        assert_eq!(obj["a"], JsonValue::Number(Num::Int(2)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_count_matching() -> Result<()> {
+        let value = winnow_parse(
+            r#"{
+            "name": "John Doe",
+            "age": 43,
+            "is_student": false,
+            "marks": [87.0, 90, -45.7, 67.9],
+            "address": {
+                "city": "New York",
+                "zip": 10001
+            }
+        }"#,
+        )?;
+        assert_eq!(
+            value.count_matching(|v| matches!(v, JsonValue::Number(_))),
+            6
+        );
+        assert_eq!(value.count_matching(|v| matches!(v, JsonValue::Null)), 0);
+        assert_eq!(value.count_matching(|_| true), 12);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_env_vars() -> Result<()> {
+        let value = winnow_parse(
+            r#"{
+            "name": "John Doe",
+            "age": 43,
+            "is_student": false,
+            "marks": [87.5, 90],
+            "address": {
+                "city": "New York",
+                "zip-code": 10001
+            },
+            "nickname": null
+        }"#,
+        )?;
+        let vars = value.to_env_vars("app");
+        let get = |name: &str| {
+            vars.iter()
+                .find(|(k, _)| k == name)
+                .map(|(_, v)| v.as_str())
+        };
+        assert_eq!(vars.len(), 8);
+        assert_eq!(get("APP_NAME"), Some("John Doe"));
+        assert_eq!(get("APP_IS_STUDENT"), Some("false"));
+        assert_eq!(get("APP_ADDRESS_CITY"), Some("New York"));
+        assert_eq!(get("APP_ADDRESS_ZIP_CODE"), Some("10001"));
+        assert_eq!(get("APP_MARKS_0"), Some("87.5"));
+        assert_eq!(get("APP_MARKS_1"), Some("90"));
+        assert_eq!(get("APP_NICKNAME"), Some(""));
+        assert_eq!(vars[0].0, "APP_ADDRESS_CITY");
+
+        let vars = JsonValue::object([("a".to_string(), JsonValue::Bool(true))]).to_env_vars("");
+        assert_eq!(vars, vec![("A".to_string(), "true".to_string())]);
+
+        Ok(())
+    }
 }
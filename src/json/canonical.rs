@@ -0,0 +1,89 @@
+use std::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
+
+use super::JsonValue;
+
+/// A `JsonValue` that can be hashed and totally ordered, e.g. to deduplicate values in a
+/// `HashSet`. Equality, ordering and hashing all use the
+/// [`JsonValue::to_sorted_compact_string`] form, so objects with the same entries are equal
+/// regardless of key order, and `1.0` equals `1`.
+#[derive(Debug, Clone)]
+pub struct CanonicalJson {
+    value: JsonValue,
+    canonical: String,
+}
+
+impl CanonicalJson {
+    pub fn new(value: JsonValue) -> Self {
+        let canonical = value.to_sorted_compact_string();
+        Self { value, canonical }
+    }
+
+    pub fn value(&self) -> &JsonValue {
+        &self.value
+    }
+
+    pub fn into_inner(self) -> JsonValue {
+        self.value
+    }
+
+    /// The canonical string form used for comparisons.
+    pub fn as_str(&self) -> &str {
+        &self.canonical
+    }
+}
+
+impl From<JsonValue> for CanonicalJson {
+    fn from(value: JsonValue) -> Self {
+        Self::new(value)
+    }
+}
+
+impl PartialEq for CanonicalJson {
+    fn eq(&self, other: &Self) -> bool {
+        self.canonical == other.canonical
+    }
+}
+
+impl Eq for CanonicalJson {}
+
+impl Hash for CanonicalJson {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.canonical.hash(state);
+    }
+}
+
+impl Ord for CanonicalJson {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical.cmp(&other.canonical)
+    }
+}
+
+impl PartialOrd for CanonicalJson {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::json::winnow_parse;
+
+    #[test]
+    fn canonical_json_should_dedup() -> anyhow::Result<()> {
+        let a = winnow_parse(r#"{"name": "John", "tags": ["a", "b"], "age": 43}"#)?;
+        let b = winnow_parse(r#"{"age": 43, "name": "John", "tags": ["a", "b"]}"#)?;
+        let c = winnow_parse(r#"{"age": 43, "name": "John", "tags": ["b", "a"]}"#)?;
+
+        let set: HashSet<CanonicalJson> = [a.clone(), b, c].into_iter().map(Into::into).collect();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&CanonicalJson::new(a)));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,237 @@
+use anyhow::{anyhow, bail, Result};
+
+use super::{
+    pointer::{parse_index, parse_pointer},
+    JsonValue,
+};
+
+/// A single JSON Patch (RFC 6902) operation, addressed by JSON Pointer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatchOp {
+    Add { path: String, value: JsonValue },
+    Remove { path: String },
+    Replace { path: String, value: JsonValue },
+}
+
+/// Apply `ops` in order. If any operation fails `doc` is left untouched.
+pub fn apply_patch(doc: &mut JsonValue, ops: &[PatchOp]) -> Result<()> {
+    let mut patched = doc.clone();
+    for op in ops {
+        apply_op(&mut patched, op)?;
+    }
+    *doc = patched;
+    Ok(())
+}
+
+/// Apply a JSON Merge Patch (RFC 7386): members of an object `patch` are merged into
+/// `target` recursively and `null` members delete the key, any other `patch` replaces
+/// `target` entirely.
+pub fn merge_patch(target: &mut JsonValue, patch: &JsonValue) {
+    let JsonValue::Object(members) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !matches!(target, JsonValue::Object(_)) {
+        *target = JsonValue::Object(Default::default());
+    }
+    if let JsonValue::Object(obj) = target {
+        for (k, v) in members {
+            if *v == JsonValue::Null {
+                obj.remove(k);
+            } else {
+                merge_patch(obj.entry(k.clone()).or_insert(JsonValue::Null), v);
+            }
+        }
+    }
+}
+
+fn apply_op(doc: &mut JsonValue, op: &PatchOp) -> Result<()> {
+    match op {
+        PatchOp::Add { path, value } => {
+            let (parent, token) = split_path(doc, path)?;
+            let Some(parent) = parent else {
+                *doc = value.clone();
+                return Ok(());
+            };
+            match parent {
+                JsonValue::Object(obj) => {
+                    obj.insert(token, value.clone());
+                }
+                // `-` appends to the end of the array
+                JsonValue::Array(arr) if token == "-" => arr.push(value.clone()),
+                JsonValue::Array(arr) => match parse_index(&token) {
+                    Some(i) if i <= arr.len() => arr.insert(i, value.clone()),
+                    _ => bail!("Invalid array index in patch path: {}", path),
+                },
+                _ => bail!("Cannot add to a scalar value: {}", path),
+            }
+        }
+        PatchOp::Remove { path } => {
+            let (Some(parent), token) = split_path(doc, path)? else {
+                bail!("Cannot remove the root document");
+            };
+            let removed = match parent {
+                JsonValue::Object(obj) => obj.remove(&token),
+                JsonValue::Array(arr) => match parse_index(&token) {
+                    Some(i) if i < arr.len() => Some(arr.remove(i)),
+                    _ => None,
+                },
+                _ => None,
+            };
+            if removed.is_none() {
+                bail!("Patch path not found: {}", path);
+            }
+        }
+        PatchOp::Replace { path, value } => {
+            let target = doc
+                .pointer_mut(path)
+                .ok_or_else(|| anyhow!("Patch path not found: {}", path))?;
+            *target = value.clone();
+        }
+    }
+    Ok(())
+}
+
+// resolve the parent container of `path` and return it along with the last token,
+// the parent is `None` when `path` refers to the whole document
+fn split_path<'a>(
+    doc: &'a mut JsonValue,
+    path: &str,
+) -> Result<(Option<&'a mut JsonValue>, String)> {
+    let mut tokens = parse_pointer(path)?;
+    let Some(token) = tokens.pop() else {
+        return Ok((None, String::new()));
+    };
+    let parent = tokens.iter().try_fold(doc, |v, t| match v {
+        JsonValue::Object(obj) => obj.get_mut(t),
+        JsonValue::Array(arr) => arr.get_mut(parse_index(t)?),
+        _ => None,
+    });
+    match parent {
+        Some(parent) => Ok((Some(parent), token)),
+        None => bail!("Patch path not found: {}", path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::winnow_parse;
+
+    const SAMPLE: &str = r#"{
+        "name": "John Doe",
+        "age": 43,
+        "marks": [87, 90],
+        "address": {
+            "city": "New York",
+            "zip": 10001
+        }
+    }"#;
+
+    fn string(s: &str) -> JsonValue {
+        JsonValue::String(s.to_string())
+    }
+
+    #[test]
+    fn apply_patch_should_work() -> Result<()> {
+        let mut doc = winnow_parse(SAMPLE)?;
+        let ops = [
+            PatchOp::Replace {
+                path: "/address/city".to_string(),
+                value: string("Boston"),
+            },
+            PatchOp::Add {
+                path: "/email".to_string(),
+                value: string("john@example.com"),
+            },
+            PatchOp::Add {
+                path: "/marks/-".to_string(),
+                value: JsonValue::Null,
+            },
+            PatchOp::Remove {
+                path: "/age".to_string(),
+            },
+        ];
+        apply_patch(&mut doc, &ops)?;
+
+        assert_eq!(doc.pointer("/address/city"), Some(&string("Boston")));
+        assert_eq!(doc.pointer("/email"), Some(&string("john@example.com")));
+        assert_eq!(doc.pointer("/marks/2"), Some(&JsonValue::Null));
+        assert_eq!(doc.pointer("/age"), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn apply_patch_with_invalid_path_should_not_partially_apply() -> Result<()> {
+        let mut doc = winnow_parse(SAMPLE)?;
+        let original = doc.clone();
+        let ops = [
+            PatchOp::Remove {
+                path: "/name".to_string(),
+            },
+            PatchOp::Replace {
+                path: "/address/street".to_string(),
+                value: string("Main St"),
+            },
+        ];
+        let err = apply_patch(&mut doc, &ops).unwrap_err();
+
+        assert!(err.to_string().contains("/address/street"));
+        assert_eq!(doc, original);
+
+        assert!(apply_patch(
+            &mut doc,
+            &[PatchOp::Add {
+                path: "/missing/key".to_string(),
+                value: JsonValue::Null,
+            }]
+        )
+        .is_err());
+        assert!(apply_patch(
+            &mut doc,
+            &[PatchOp::Remove {
+                path: "".to_string()
+            }]
+        )
+        .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn merge_patch_should_work() -> Result<()> {
+        let mut doc = winnow_parse(SAMPLE)?;
+        let patch = winnow_parse(
+            r#"{"age": null, "email": "jd@example.com", "address": {"zip": null, "street": "Main St"}}"#,
+        )?;
+        merge_patch(&mut doc, &patch);
+        let expected = winnow_parse(
+            r#"{
+                "name": "John Doe",
+                "email": "jd@example.com",
+                "marks": [87, 90],
+                "address": {"city": "New York", "street": "Main St"}
+            }"#,
+        )?;
+        assert_eq!(doc, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn merge_patch_should_replace_non_objects() -> Result<()> {
+        let mut doc = winnow_parse(r#"{"name": "John Doe", "marks": [87, 90]}"#)?;
+        let patch = winnow_parse(r#"{"name": {"first": "John", "middle": null}, "marks": [1]}"#)?;
+        merge_patch(&mut doc, &patch);
+        assert_eq!(
+            doc,
+            winnow_parse(r#"{"name": {"first": "John"}, "marks": [1]}"#)?
+        );
+
+        merge_patch(&mut doc, &JsonValue::Bool(true));
+        assert_eq!(doc, JsonValue::Bool(true));
+
+        Ok(())
+    }
+}
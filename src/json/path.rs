@@ -0,0 +1,151 @@
+use anyhow::{anyhow, Result};
+use winnow::{
+    ascii::dec_int,
+    combinator::{alt, delimited, preceded, repeat},
+    token::take_while,
+    PResult, Parser,
+};
+
+use super::{parse_string, JsonValue};
+use crate::parsers::quoted;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Key(String),
+    // negative indices count from the end
+    Index(i64),
+    Wildcard,
+}
+
+impl JsonValue {
+    /// Evaluate a JSONPath subset query against the value: `$` followed by `.name`,
+    /// `['name']`, `[0]`, `[-1]`, `.*` or `[*]` segments. Wildcards visit object members
+    /// in key order; missing members and type mismatches simply match nothing.
+    pub fn select(&self, path: &str) -> Result<Vec<&JsonValue>> {
+        let segments = parse_path(path)?;
+        let mut current = vec![self];
+        for segment in &segments {
+            current = current
+                .into_iter()
+                .flat_map(|v| select_children(v, segment))
+                .collect();
+        }
+        Ok(current)
+    }
+
+    /// The string values matched by [`JsonValue::select`], other matches are ignored and an
+    /// invalid `path` matches nothing.
+    pub fn select_strings(&self, path: &str) -> Vec<String> {
+        self.select(path)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|v| match v {
+                JsonValue::String(s) => Some(s.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+fn select_children<'a>(value: &'a JsonValue, segment: &Segment) -> Vec<&'a JsonValue> {
+    match (segment, value) {
+        (Segment::Key(key), JsonValue::Object(obj)) => obj.get(key).into_iter().collect(),
+        (Segment::Index(i), JsonValue::Array(arr)) => {
+            let i = if *i < 0 { arr.len() as i64 + i } else { *i };
+            usize::try_from(i)
+                .ok()
+                .and_then(|i| arr.get(i))
+                .into_iter()
+                .collect()
+        }
+        (Segment::Wildcard, JsonValue::Array(arr)) => arr.iter().collect(),
+        (Segment::Wildcard, JsonValue::Object(obj)) => {
+            let mut entries: Vec<_> = obj.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            entries.into_iter().map(|(_, v)| v).collect()
+        }
+        _ => vec![],
+    }
+}
+
+fn parse_path(path: &str) -> Result<Vec<Segment>> {
+    preceded('$', repeat(0.., parse_segment))
+        .parse(path)
+        .map_err(|e| anyhow!("invalid JSONPath {:?}: {}", path, e))
+}
+
+fn parse_segment(input: &mut &str) -> PResult<Segment> {
+    let name = take_while(1.., |c: char| c.is_alphanumeric() || c == '_' || c == '-');
+    alt((
+        preceded(
+            '.',
+            alt((
+                '*'.value(Segment::Wildcard),
+                name.map(|s: &str| Segment::Key(s.to_string())),
+            )),
+        ),
+        delimited(
+            '[',
+            alt((
+                '*'.value(Segment::Wildcard),
+                dec_int.map(Segment::Index),
+                quoted('\'', false).map(Segment::Key),
+                parse_string.map(Segment::Key),
+            )),
+            ']',
+        ),
+    ))
+    .parse_next(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::winnow_parse;
+
+    #[test]
+    fn parse_path_should_work() -> Result<()> {
+        assert_eq!(
+            parse_path("$.items[*]['a b'][-1].*")?,
+            vec![
+                Segment::Key("items".to_string()),
+                Segment::Wildcard,
+                Segment::Key("a b".to_string()),
+                Segment::Index(-1),
+                Segment::Wildcard,
+            ]
+        );
+        assert_eq!(parse_path("$")?, vec![]);
+        assert!(parse_path("items").is_err());
+        assert!(parse_path("$.").is_err());
+        assert!(parse_path("$[1").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn select_strings_should_work() -> Result<()> {
+        let value = winnow_parse(
+            r#"{"items": [
+                {"name": "apple", "price": 1.5},
+                {"name": "pear", "tags": ["green", 7, "ripe"]},
+                {"name": 42}
+            ]}"#,
+        )?;
+        assert_eq!(
+            value.select_strings("$.items[*].name"),
+            vec!["apple", "pear"]
+        );
+        assert_eq!(
+            value.select_strings("$.items[1].tags[*]"),
+            vec!["green", "ripe"]
+        );
+        assert_eq!(value.select_strings("$.items[0].*"), vec!["apple"]);
+        assert_eq!(value.select_strings("$['items'][-2].name"), vec!["pear"]);
+        assert!(value.select_strings("$.missing[*]").is_empty());
+        assert!(value.select_strings("items").is_empty());
+        assert_eq!(value.select("$.items[*]")?.len(), 3);
+
+        Ok(())
+    }
+}
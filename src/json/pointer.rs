@@ -0,0 +1,561 @@
+use anyhow::{anyhow, bail, Result};
+use winnow::{
+    ascii::multispace0,
+    combinator::{alt, delimited, dispatch, peek, preceded, repeat, separated},
+    token::{any, take_till, take_while},
+    PResult, Parser,
+};
+
+use super::{parse_string, parse_value, JsonValue};
+
+/// Parse only the value at the JSON Pointer `path` out of `input`.
+///
+/// Subtrees that aren't on the path are skipped over without being built, and scanning
+/// stops as soon as the target is found, so the rest of the document isn't validated.
+pub fn seek_pointer(input: &str, path: &str) -> Result<Option<JsonValue>> {
+    let tokens = parse_pointer(path)?;
+    let input = &mut (&*input);
+    let ret = preceded(multispace0, |i: &mut &str| seek_value(i, &tokens))
+        .parse_next(input)
+        .map_err(|e| anyhow!("Failed to parse JSON: {:?}", e));
+    ret
+}
+
+/// How array segments of a JSON Pointer are interpreted.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PointerMode {
+    /// Plain RFC 6901: only non-negative indices without leading zeros.
+    #[default]
+    Strict,
+    /// Also accept negative indices counting from the end, `-1` being the last element.
+    Lenient,
+}
+
+impl JsonValue {
+    /// Look up the value at the JSON Pointer `path`, `None` if it is missing or invalid.
+    pub fn pointer(&self, path: &str) -> Option<&JsonValue> {
+        self.pointer_with(path, PointerMode::Strict)
+    }
+
+    /// Look up the value at the JSON Pointer `path`, falling back to `default` when missing.
+    pub fn pointer_or<'a>(&'a self, path: &str, default: &'a JsonValue) -> &'a JsonValue {
+        self.pointer(path).unwrap_or(default)
+    }
+
+    /// Look up the value at the JSON Pointer `path`, falling back to `null` when missing.
+    pub fn pointer_or_default(&self, path: &str) -> &JsonValue {
+        static NULL: JsonValue = JsonValue::Null;
+        self.pointer_or(path, &NULL)
+    }
+
+    /// Like [`JsonValue::pointer`], interpreting array segments according to `mode`.
+    pub fn pointer_with(&self, path: &str, mode: PointerMode) -> Option<&JsonValue> {
+        parse_pointer(path)
+            .ok()?
+            .iter()
+            .try_fold(self, |v, token| match v {
+                JsonValue::Object(obj) => obj.get(token),
+                JsonValue::Array(arr) => arr.get(resolve_index(token, arr.len(), mode)?),
+                _ => None,
+            })
+    }
+
+    /// Mutable version of [`JsonValue::pointer`].
+    pub fn pointer_mut(&mut self, path: &str) -> Option<&mut JsonValue> {
+        parse_pointer(path)
+            .ok()?
+            .iter()
+            .try_fold(self, |v, token| match v {
+                JsonValue::Object(obj) => obj.get_mut(token),
+                JsonValue::Array(arr) => arr.get_mut(parse_index(token)?),
+                _ => None,
+            })
+    }
+
+    /// Set the value at the JSON Pointer `path`, creating empty objects for missing segments
+    /// along the way. Array segments must name an existing index, and traversing through a
+    /// scalar is an error.
+    pub fn set_path(&mut self, path: &str, value: JsonValue) -> Result<()> {
+        let tokens = parse_pointer(path)?;
+        let mut current = self;
+        for (i, token) in tokens.iter().enumerate() {
+            current = match current {
+                JsonValue::Object(obj) => obj
+                    .entry(token.clone())
+                    .or_insert_with(|| JsonValue::Object(Default::default())),
+                JsonValue::Array(arr) => {
+                    let len = arr.len();
+                    parse_index(token)
+                        .and_then(|index| arr.get_mut(index))
+                        .ok_or_else(|| {
+                            anyhow!("index {:?} out of bounds for length {}", token, len)
+                        })?
+                }
+                v => bail!(
+                    "can't set {:?} through a {:?} at {:?}",
+                    path,
+                    v.json_type(),
+                    to_pointer(&tokens[..i])
+                ),
+            };
+        }
+        *current = value;
+        Ok(())
+    }
+
+    /// Compare two trees, treating the values at the `ignore_paths` JSON Pointers as equal
+    /// even when they differ or are missing on one side. Invalid pointers are ignored.
+    pub fn eq_ignoring(&self, other: &JsonValue, ignore_paths: &[&str]) -> bool {
+        let ignored: Vec<Vec<String>> = ignore_paths
+            .iter()
+            .filter_map(|path| parse_pointer(path).ok())
+            .collect();
+        eq_at(self, other, &mut vec![], &ignored)
+    }
+}
+
+/// Compare two trees, on inequality describing the first difference and the JSON Pointer of
+/// the node where it occurs. Object keys are visited in sorted order.
+pub fn assert_json_eq(a: &JsonValue, b: &JsonValue) -> Result<(), String> {
+    match first_diff(a, b, &mut vec![]) {
+        Some((path, diff)) => Err(format!("JSON differs at {:?}: {}", path, diff)),
+        None => Ok(()),
+    }
+}
+
+fn first_diff(a: &JsonValue, b: &JsonValue, path: &mut Vec<String>) -> Option<(String, String)> {
+    let pointer = to_pointer(path);
+    let mut child_diff = |token: String, a: &JsonValue, b: &JsonValue| {
+        path.push(token);
+        let ret = first_diff(a, b, path);
+        path.pop();
+        ret
+    };
+    let diff = match (a, b) {
+        _ if a.json_type() != b.json_type() => {
+            format!("type mismatch, {:?} != {:?}", a.json_type(), b.json_type())
+        }
+        (JsonValue::Array(x), JsonValue::Array(y)) => {
+            if let Some(diff) = x
+                .iter()
+                .zip(y)
+                .enumerate()
+                .find_map(|(i, (x, y))| child_diff(i.to_string(), x, y))
+            {
+                return Some(diff);
+            }
+            if x.len() == y.len() {
+                return None;
+            }
+            format!("array length mismatch, {} != {}", x.len(), y.len())
+        }
+        (JsonValue::Object(x), JsonValue::Object(y)) => {
+            let mut keys: Vec<&String> = x.keys().chain(y.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            return keys.into_iter().find_map(|k| match (x.get(k), y.get(k)) {
+                (Some(x), Some(y)) => child_diff(k.clone(), x, y),
+                (_, None) => Some((pointer.clone(), format!("key {:?} missing on the right", k))),
+                (None, _) => Some((pointer.clone(), format!("key {:?} missing on the left", k))),
+            });
+        }
+        _ if a == b => return None,
+        _ => format!(
+            "value mismatch, {} != {}",
+            a.to_sorted_compact_string(),
+            b.to_sorted_compact_string()
+        ),
+    };
+    Some((pointer, diff))
+}
+
+fn eq_at(a: &JsonValue, b: &JsonValue, path: &mut Vec<String>, ignored: &[Vec<String>]) -> bool {
+    if ignored.contains(path) {
+        return true;
+    }
+    let mut child_eq = |token: String, a: Option<&JsonValue>, b: Option<&JsonValue>| {
+        path.push(token);
+        let ret = match (a, b) {
+            (Some(a), Some(b)) => eq_at(a, b, path, ignored),
+            _ => ignored.contains(path),
+        };
+        path.pop();
+        ret
+    };
+    match (a, b) {
+        (JsonValue::Array(a), JsonValue::Array(b)) => {
+            a.len() == b.len() && (0..a.len()).all(|i| child_eq(i.to_string(), a.get(i), b.get(i)))
+        }
+        (JsonValue::Object(a), JsonValue::Object(b)) => a
+            .keys()
+            .chain(b.keys().filter(|k| !a.contains_key(*k)))
+            .all(|k| child_eq(k.clone(), a.get(k), b.get(k))),
+        (a, b) => a == b,
+    }
+}
+
+/// Split a JSON Pointer (RFC 6901) into its unescaped reference tokens.
+pub(crate) fn parse_pointer(path: &str) -> Result<Vec<String>> {
+    if path.is_empty() {
+        return Ok(vec![]);
+    }
+    let Some(rest) = path.strip_prefix('/') else {
+        bail!("JSON pointer must start with '/': {}", path);
+    };
+    rest.split('/').map(unescape_token).collect()
+}
+
+/// Join reference tokens back into a JSON Pointer, escaping them as needed.
+pub(crate) fn to_pointer(tokens: &[String]) -> String {
+    tokens
+        .iter()
+        .map(|token| format!("/{}", token.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+// `~1` stands for `/` and `~0` for `~`, any other `~` sequence is invalid
+fn unescape_token(token: &str) -> Result<String> {
+    let mut ret = String::with_capacity(token.len());
+    let mut chars = token.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '~' => match chars.next() {
+                Some('0') => ret.push('~'),
+                Some('1') => ret.push('/'),
+                _ => bail!("invalid escape in JSON pointer token: {}", token),
+            },
+            c => ret.push(c),
+        }
+    }
+    Ok(ret)
+}
+
+// array indices are plain decimal numbers without leading zeros
+pub(crate) fn parse_index(token: &str) -> Option<usize> {
+    if token.is_empty() || (token.len() > 1 && token.starts_with('0')) {
+        return None;
+    }
+    if !token.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    token.parse().ok()
+}
+
+fn resolve_index(token: &str, len: usize, mode: PointerMode) -> Option<usize> {
+    match (mode, token.strip_prefix('-')) {
+        (PointerMode::Lenient, Some(rest)) => {
+            len.checked_sub(parse_index(rest).filter(|&i| i > 0)?)
+        }
+        _ => parse_index(token),
+    }
+}
+
+fn seek_value(input: &mut &str, tokens: &[String]) -> PResult<Option<JsonValue>> {
+    let Some((token, rest)) = tokens.split_first() else {
+        return parse_value(input).map(Some);
+    };
+    if input.starts_with('{') {
+        seek_object(input, token, rest)
+    } else if input.starts_with('[') {
+        seek_array(input, token, rest)
+    } else {
+        skip_value(input)?;
+        Ok(None)
+    }
+}
+
+fn seek_object(input: &mut &str, token: &str, rest: &[String]) -> PResult<Option<JsonValue>> {
+    ('{', multispace0).parse_next(input)?;
+    if input.starts_with('}') {
+        return Ok(None);
+    }
+    loop {
+        let key = parse_string(input)?;
+        (multispace0, ':', multispace0).parse_next(input)?;
+        if key == token {
+            return seek_value(input, rest);
+        }
+        skip_value(input)?;
+        multispace0(input)?;
+        if alt((',', '}')).parse_next(input)? == '}' {
+            return Ok(None);
+        }
+        multispace0(input)?;
+    }
+}
+
+fn seek_array(input: &mut &str, token: &str, rest: &[String]) -> PResult<Option<JsonValue>> {
+    let Some(target) = parse_index(token) else {
+        skip_value(input)?;
+        return Ok(None);
+    };
+    ('[', multispace0).parse_next(input)?;
+    if input.starts_with(']') {
+        return Ok(None);
+    }
+    let mut index = 0;
+    loop {
+        if index == target {
+            return seek_value(input, rest);
+        }
+        skip_value(input)?;
+        multispace0(input)?;
+        if alt((',', ']')).parse_next(input)? == ']' {
+            return Ok(None);
+        }
+        multispace0(input)?;
+        index += 1;
+    }
+}
+
+// walks over a value without allocating anything for it
+fn skip_value(input: &mut &str) -> PResult<()> {
+    dispatch! {peek(any);
+        '"' => skip_string,
+        '[' => skip_array,
+        '{' => skip_object,
+        't' => "true".void(),
+        'f' => "false".void(),
+        'n' => "null".void(),
+        _ => take_while(1.., |c: char| c.is_ascii_digit() || "+-.eE".contains(c)).void(),
+    }
+    .parse_next(input)
+}
+
+fn skip_string(input: &mut &str) -> PResult<()> {
+    let content = repeat(
+        0..,
+        alt((
+            take_till(1.., ['"', '\\']).void(),
+            preceded('\\', any).void(),
+        )),
+    )
+    .map(|()| ());
+    delimited('"', content, '"').parse_next(input)
+}
+
+fn skip_array(input: &mut &str) -> PResult<()> {
+    let item = delimited(multispace0, skip_value, multispace0);
+    let items = separated(0.., item, ',').map(|()| ());
+    delimited(('[', multispace0), items, ']').parse_next(input)
+}
+
+fn skip_object(input: &mut &str) -> PResult<()> {
+    let pair = (
+        multispace0,
+        skip_string,
+        multispace0,
+        ':',
+        multispace0,
+        skip_value,
+        multispace0,
+    );
+    let pairs = separated(0.., pair.void(), ',').map(|()| ());
+    delimited(('{', multispace0), pairs, '}').parse_next(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::{Num, PARSED_VALUES};
+
+    const SAMPLE: &str = r#"{
+        "name": "John Doe",
+        "age": 43,
+        "is_student": false,
+        "marks": [87.0, 90, -45.7, 67.9],
+        "empty": [],
+        "address": {
+            "city": "New York",
+            "zip": 10001
+        }
+    }"#;
+
+    #[test]
+    fn seek_pointer_should_work() -> Result<()> {
+        PARSED_VALUES.with(|c| c.set(0));
+        let city = seek_pointer(SAMPLE, "/address/city")?;
+        assert_eq!(city, Some(JsonValue::String("New York".to_string())));
+        // only the target itself was materialized, `marks` and friends were skipped
+        assert_eq!(PARSED_VALUES.with(|c| c.get()), 1);
+
+        let mark = seek_pointer(SAMPLE, "/marks/1")?;
+        assert_eq!(mark, Some(JsonValue::Number(Num::Int(90))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn seek_missing_pointer_should_return_none() -> Result<()> {
+        assert_eq!(seek_pointer(SAMPLE, "/address/street")?, None);
+        assert_eq!(seek_pointer(SAMPLE, "/marks/4")?, None);
+        assert_eq!(seek_pointer(SAMPLE, "/marks/01")?, None);
+        assert_eq!(seek_pointer(SAMPLE, "/name/first")?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn pointer_should_work() -> Result<()> {
+        let mut value = crate::json::winnow_parse(
+            r#"{"marks": [87.0, 90], "address": {"city": "New York", "zip": 10001}}"#,
+        )?;
+        assert_eq!(
+            value.pointer("/address/zip"),
+            Some(&JsonValue::Number(Num::Int(10001)))
+        );
+        assert_eq!(value.pointer(""), Some(&value));
+        assert_eq!(value.pointer("/marks/9"), None);
+        assert_eq!(value.pointer("address"), None);
+
+        *value.pointer_mut("/marks/0").unwrap() = JsonValue::Null;
+        assert_eq!(value.pointer("/marks/0"), Some(&JsonValue::Null));
+
+        Ok(())
+    }
+
+    #[test]
+    fn eq_ignoring_should_work() -> Result<()> {
+        let a = crate::json::winnow_parse(
+            r#"{"id": 1, "meta": {"created_at": "2024-01-01", "tags": ["a"]}}"#,
+        )?;
+        let b = crate::json::winnow_parse(
+            r#"{"id": 1, "meta": {"created_at": "2024-06-30", "tags": ["a"]}}"#,
+        )?;
+        assert_ne!(a, b);
+        assert!(a.eq_ignoring(&b, &["/meta/created_at"]));
+        assert!(!a.eq_ignoring(&b, &["/meta/tags"]));
+
+        let c = crate::json::winnow_parse(r#"{"id": 1, "meta": {"tags": ["a"]}}"#)?;
+        assert!(a.eq_ignoring(&c, &["/meta/created_at"]));
+        assert!(c.eq_ignoring(&a, &["/meta/created_at"]));
+        assert!(!a.eq_ignoring(&c, &[]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_pointer_should_work() -> Result<()> {
+        assert_eq!(parse_pointer("")?, Vec::<String>::new());
+        assert_eq!(parse_pointer("/a~1b/m~0n/")?, vec!["a/b", "m~n", ""]);
+        assert!(parse_pointer("a/b").is_err());
+        assert!(parse_pointer("/a~2").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn pointer_with_negative_index_should_work() -> Result<()> {
+        let value = crate::json::winnow_parse(r#"{"marks": [87.0, 90, 67.9]}"#)?;
+        let last = Some(&JsonValue::Number(Num::Float(67.9)));
+        assert_eq!(value.pointer_with("/marks/-1", PointerMode::Lenient), last);
+        assert_eq!(
+            value.pointer_with("/marks/-3", PointerMode::Lenient),
+            Some(&JsonValue::Number(Num::Float(87.0)))
+        );
+        assert_eq!(value.pointer_with("/marks/-4", PointerMode::Lenient), None);
+        assert_eq!(value.pointer_with("/marks/-0", PointerMode::Lenient), None);
+        assert_eq!(value.pointer_with("/marks/-", PointerMode::Lenient), None);
+
+        assert_eq!(value.pointer("/marks/-1"), None);
+        assert_eq!(value.pointer_with("/marks/-1", PointerMode::Strict), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn pointer_or_should_work() -> Result<()> {
+        let value = crate::json::winnow_parse(r#"{"server": {"port": 8080}}"#)?;
+        let default = JsonValue::Number(Num::Int(80));
+        assert_eq!(
+            value.pointer_or("/server/port", &default),
+            &JsonValue::Number(Num::Int(8080))
+        );
+        assert_eq!(value.pointer_or("/server/host", &default), &default);
+
+        assert_eq!(
+            value.pointer_or_default("/server/port"),
+            &JsonValue::Number(Num::Int(8080))
+        );
+        assert_eq!(value.pointer_or_default("/server/host"), &JsonValue::Null);
+
+        Ok(())
+    }
+
+    #[test]
+    fn assert_json_eq_should_work() -> Result<()> {
+        let a = crate::json::winnow_parse(
+            r#"{"name": "John", "address": {"city": "New York", "zip": 10001}, "marks": [1, 2]}"#,
+        )?;
+        assert_eq!(assert_json_eq(&a, &a.clone()), Ok(()));
+
+        let b = crate::json::winnow_parse(
+            r#"{"name": "John", "address": {"city": "New York", "zip": 10002}, "marks": [1, 2]}"#,
+        )?;
+        assert_eq!(
+            assert_json_eq(&a, &b),
+            Err(r#"JSON differs at "/address/zip": value mismatch, 10001 != 10002"#.to_string())
+        );
+
+        let b = crate::json::winnow_parse(
+            r#"{"name": "John", "address": {"city": "New York", "zip": "10001"}, "marks": [1, 2, 3]}"#,
+        )?;
+        assert_eq!(
+            assert_json_eq(&a, &b),
+            Err(r#"JSON differs at "/address/zip": type mismatch, Number != String"#.to_string())
+        );
+
+        let b = crate::json::winnow_parse(
+            r#"{"name": "John", "address": {"zip": 10001}, "marks": [1, 2, 3]}"#,
+        )?;
+        assert_eq!(
+            assert_json_eq(&a, &b),
+            Err(r#"JSON differs at "/address": key "city" missing on the right"#.to_string())
+        );
+
+        let b = crate::json::winnow_parse(
+            r#"{"name": "John", "address": {"city": "New York", "zip": 10001}, "marks": [1, 2, 3]}"#,
+        )?;
+        assert_eq!(
+            assert_json_eq(&a, &b),
+            Err(r#"JSON differs at "/marks": array length mismatch, 2 != 3"#.to_string())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn set_path_should_work() -> Result<()> {
+        let mut value = JsonValue::Object(Default::default());
+        value.set_path("/a/b/c", JsonValue::Bool(true))?;
+        assert_eq!(
+            value,
+            crate::json::winnow_parse(r#"{"a": {"b": {"c": true}}}"#)?
+        );
+
+        let mut value = crate::json::winnow_parse(
+            r#"{"server": {"port": 80, "hosts": ["a", "b"]}, "name": "x"}"#,
+        )?;
+        value.set_path("/server/port", JsonValue::Number(Num::Int(8080)))?;
+        value.set_path("/server/tls/enabled", JsonValue::Bool(false))?;
+        value.set_path("/server/hosts/1", JsonValue::String("c".to_string()))?;
+        assert_eq!(
+            value,
+            crate::json::winnow_parse(
+                r#"{"server": {"port": 8080, "hosts": ["a", "c"], "tls": {"enabled": false}}, "name": "x"}"#,
+            )?
+        );
+
+        let err = value.set_path("/name/first", JsonValue::Null).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"can't set "/name/first" through a String at "/name""#
+        );
+        assert!(value.set_path("/server/hosts/2", JsonValue::Null).is_err());
+
+        value.set_path("", JsonValue::Null)?;
+        assert_eq!(value, JsonValue::Null);
+
+        Ok(())
+    }
+}
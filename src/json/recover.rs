@@ -0,0 +1,207 @@
+use std::{collections::HashMap, fmt};
+
+use winnow::{ascii::multispace0, combinator::alt, Parser};
+
+use super::{parse_bool, parse_null, parse_num, parse_string, JsonValue};
+use crate::parsers::line_column;
+
+/// A problem found by [`parse_json_recover`], positions are 1-based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+/// Parse a JSON document, recovering from missing and extra commas and missing colons.
+///
+/// Every problem found is reported. Parsing stops at the first unrecoverable error, in which
+/// case the tree built so far is returned with the unfinished containers closed.
+pub fn parse_json_recover(input: &str) -> (Option<JsonValue>, Vec<ParseError>) {
+    let mut parser = Recover {
+        source: input,
+        errors: vec![],
+        fatal: false,
+    };
+    let s = &mut (&*input);
+    let value = parser.parse_value(s);
+    skip_ws(s);
+    if !parser.fatal && !s.is_empty() {
+        parser.error(s, "unexpected trailing characters");
+    }
+    (value, parser.errors)
+}
+
+struct Recover<'a> {
+    source: &'a str,
+    errors: Vec<ParseError>,
+    // set once an error can't be recovered from, unwinds all open containers
+    fatal: bool,
+}
+
+impl Recover<'_> {
+    fn error(&mut self, s: &str, message: &str) {
+        let offset = self.source.len() - s.len();
+        let (line, column) = line_column(self.source, offset);
+        self.errors.push(ParseError {
+            offset,
+            line,
+            column,
+            message: message.to_string(),
+        });
+    }
+
+    fn parse_value(&mut self, s: &mut &str) -> Option<JsonValue> {
+        skip_ws(s);
+        if s.starts_with('[') {
+            return Some(self.parse_array(s));
+        }
+        if s.starts_with('{') {
+            return Some(self.parse_object(s));
+        }
+        let scalar = alt((
+            parse_null.value(JsonValue::Null),
+            parse_bool.map(JsonValue::Bool),
+            parse_num.map(JsonValue::Number),
+            parse_string.map(JsonValue::String),
+        ))
+        .parse_next(s);
+        if scalar.is_err() {
+            self.error(s, "expected a value");
+            self.fatal = true;
+        }
+        scalar.ok()
+    }
+
+    fn parse_array(&mut self, s: &mut &str) -> JsonValue {
+        *s = &s[1..];
+        let mut arr = vec![];
+        while self.next_item(s, ']', arr.is_empty()) {
+            match self.parse_value(s) {
+                Some(v) => arr.push(v),
+                None => break,
+            }
+        }
+        JsonValue::Array(arr)
+    }
+
+    fn parse_object(&mut self, s: &mut &str) -> JsonValue {
+        *s = &s[1..];
+        let mut obj = HashMap::new();
+        while self.next_item(s, '}', obj.is_empty()) {
+            let Ok(key) = parse_string(s) else {
+                self.error(s, "expected a string key");
+                self.fatal = true;
+                break;
+            };
+            skip_ws(s);
+            match s.strip_prefix(':') {
+                Some(rest) => *s = rest,
+                None => self.error(s, "missing colon"),
+            }
+            match self.parse_value(s) {
+                Some(v) => obj.insert(key, v),
+                None => break,
+            };
+        }
+        JsonValue::Object(obj)
+    }
+
+    // Consume what sits between container items and return whether another item follows.
+    // Stray, doubled and trailing commas are reported and skipped, a missing comma is
+    // reported but the next item is still parsed.
+    fn next_item(&mut self, s: &mut &str, close: char, first: bool) -> bool {
+        let mut commas = 0;
+        loop {
+            if self.fatal {
+                return false;
+            }
+            skip_ws(s);
+            if let Some(rest) = s.strip_prefix(close) {
+                *s = rest;
+                return false;
+            }
+            if s.is_empty() {
+                self.error(s, "unexpected end of input");
+                self.fatal = true;
+                return false;
+            }
+            if let Some(rest) = s.strip_prefix(',') {
+                if first || commas > 0 || rest.trim_start().starts_with(close) {
+                    self.error(s, "unexpected comma");
+                }
+                commas += 1;
+                *s = rest;
+                continue;
+            }
+            if !first && commas == 0 {
+                self.error(s, "missing comma");
+            }
+            return true;
+        }
+    }
+}
+
+fn skip_ws(s: &mut &str) {
+    let _ = multispace0::<_, winnow::error::ContextError>.parse_next(s);
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::{winnow_parse, Num};
+
+    #[test]
+    fn parse_json_recover_should_report_all_errors() -> anyhow::Result<()> {
+        let s = "{\n  \"name\": \"John\"\n  \"marks\": [1, 2,, 3,],\n  \"age\": 43\n}";
+        let (value, errors) = parse_json_recover(s);
+
+        assert_eq!(
+            value,
+            Some(winnow_parse(
+                r#"{"name": "John", "marks": [1, 2, 3], "age": 43}"#
+            )?)
+        );
+        let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "missing comma at line 3, column 3",
+                "unexpected comma at line 3, column 18",
+                "unexpected comma at line 3, column 21",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_json_recover_should_return_partial_tree() {
+        let (value, errors) = parse_json_recover(r#"[1, {"a": @}, 3]"#);
+        assert_eq!(
+            value,
+            Some(JsonValue::Array(vec![
+                JsonValue::Number(Num::Int(1)),
+                JsonValue::Object(HashMap::new()),
+            ]))
+        );
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message, "expected a value");
+        assert_eq!(errors[0].offset, 10);
+
+        let (value, errors) = parse_json_recover(r#"{"a": 1}"#);
+        assert!(value.is_some());
+        assert!(errors.is_empty());
+    }
+}
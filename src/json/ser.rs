@@ -0,0 +1,383 @@
+use std::fmt::Write;
+
+use anyhow::{bail, Result};
+
+use super::{pointer::to_pointer, JsonValue, Num};
+
+/// How the serializer writes `Num::Float` values, the default matches Rust's `f64` display.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct NumberFormat {
+    /// Write integral floats with a trailing `.0`, e.g. `1.0` rather than `1`.
+    pub float_trailing_zero: bool,
+    /// Write fractional floats with exactly this many digits after the decimal point.
+    pub precision: Option<usize>,
+    /// Use exponent notation, e.g. `1e21`, for magnitudes at or above this threshold.
+    pub exponent_threshold: Option<f64>,
+}
+
+impl JsonValue {
+    /// Render the value with lexicographically sorted object keys, indenting each nesting
+    /// level by `indent` spaces. Array order is preserved.
+    pub fn to_pretty_sorted(&self, indent: usize) -> String {
+        self.to_string_with(Some(indent), NumberFormat::default())
+    }
+
+    /// Render the value on a single line with sorted object keys and no insignificant
+    /// whitespace, so equal values always produce the same string. Numbers use Rust's float
+    /// formatting, use [`JsonValue::to_canonical_json`] for the interoperable RFC 8785 form.
+    pub fn to_sorted_compact_string(&self) -> String {
+        self.to_string_with(None, NumberFormat::default())
+    }
+
+    /// Render the value with sorted object keys, indented like [`JsonValue::to_pretty_sorted`]
+    /// or on a single line when `indent` is `None`, writing floats according to `numbers`.
+    pub fn to_string_with(&self, indent: Option<usize>, numbers: NumberFormat) -> String {
+        let mut out = String::new();
+        write_value(&mut out, self, Style::Plain { indent, numbers }, 0);
+        out
+    }
+
+    /// Render the value using the JSON Canonicalization Scheme (RFC 8785): keys sorted by
+    /// UTF-16 code units, no insignificant whitespace and numbers formatted like ECMAScript.
+    /// Non-finite floats have no JSON representation and are an error. Unlike
+    /// [`JsonValue::to_sorted_compact_string`] the output is meant for signing and hashing
+    /// across implementations.
+    pub fn to_canonical_json(&self) -> Result<String> {
+        if let Some(path) = find_non_finite(self, &mut vec![]) {
+            bail!("can't canonicalize a non-finite number at {:?}", path);
+        }
+        let mut out = String::new();
+        write_value(&mut out, self, Style::Jcs, 0);
+        Ok(out)
+    }
+}
+
+// JSON Pointer of the first NaN or infinity, objects are searched in key order
+fn find_non_finite(value: &JsonValue, path: &mut Vec<String>) -> Option<String> {
+    let children: Vec<(String, &JsonValue)> = match value {
+        JsonValue::Number(Num::Float(v)) if !v.is_finite() => return Some(to_pointer(path)),
+        JsonValue::Array(arr) => arr
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (i.to_string(), v))
+            .collect(),
+        JsonValue::Object(obj) => {
+            let mut entries: Vec<_> = obj.iter().map(|(k, v)| (k.clone(), v)).collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            entries
+        }
+        _ => return None,
+    };
+    for (token, child) in children {
+        path.push(token);
+        if let Some(pointer) = find_non_finite(child, path) {
+            return Some(pointer);
+        }
+        path.pop();
+    }
+    None
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Style {
+    Plain {
+        indent: Option<usize>,
+        numbers: NumberFormat,
+    },
+    Jcs,
+}
+
+impl Style {
+    fn indent(self) -> Option<usize> {
+        match self {
+            Style::Plain { indent, .. } => indent,
+            Style::Jcs => None,
+        }
+    }
+}
+
+fn write_value(out: &mut String, value: &JsonValue, style: Style, level: usize) {
+    let indent = style.indent();
+    match value {
+        JsonValue::Null => out.push_str("null"),
+        JsonValue::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        JsonValue::Number(n) => match style {
+            Style::Plain { numbers, .. } => write_num(out, n, &numbers),
+            Style::Jcs => write_es_number(out, n.as_f64()),
+        },
+        JsonValue::String(s) => write_string(out, s),
+        JsonValue::Array(arr) if arr.is_empty() => out.push_str("[]"),
+        JsonValue::Array(arr) => {
+            out.push('[');
+            for (i, v) in arr.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_newline(out, indent, level + 1);
+                write_value(out, v, style, level + 1);
+            }
+            write_newline(out, indent, level);
+            out.push(']');
+        }
+        JsonValue::Object(obj) if obj.is_empty() => out.push_str("{}"),
+        JsonValue::Object(obj) => {
+            let mut entries: Vec<_> = obj.iter().collect();
+            match style {
+                Style::Jcs => entries.sort_by(|a, b| a.0.encode_utf16().cmp(b.0.encode_utf16())),
+                _ => entries.sort_by(|a, b| a.0.cmp(b.0)),
+            }
+            out.push('{');
+            for (i, (k, v)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_newline(out, indent, level + 1);
+                write_string(out, k);
+                out.push_str(if indent.is_some() { ": " } else { ":" });
+                write_value(out, v, style, level + 1);
+            }
+            write_newline(out, indent, level);
+            out.push('}');
+        }
+    }
+}
+
+fn write_newline(out: &mut String, indent: Option<usize>, level: usize) {
+    if let Some(indent) = indent {
+        out.push('\n');
+        out.push_str(&" ".repeat(indent * level));
+    }
+}
+
+fn write_num(out: &mut String, n: &Num, format: &NumberFormat) {
+    let v = match n {
+        Num::Int(v) => {
+            let _ = write!(out, "{}", v);
+            return;
+        }
+        // JSON has no NaN or infinities, write them like `Num`'s `Display` does
+        Num::Float(v) if !v.is_finite() => {
+            out.push_str("null");
+            return;
+        }
+        Num::Float(v) => *v,
+    };
+    let use_exponent = format
+        .exponent_threshold
+        .is_some_and(|t| v != 0.0 && v.abs() >= t);
+    let _ = match (use_exponent, format.precision) {
+        (true, Some(p)) => write!(out, "{:.*e}", p, v),
+        (true, None) => write!(out, "{:e}", v),
+        (false, Some(p)) if v.fract() != 0.0 => write!(out, "{:.*}", p, v),
+        (false, _) if format.float_trailing_zero && v.fract() == 0.0 => {
+            write!(out, "{}.0", v)
+        }
+        (false, _) => write!(out, "{}", v),
+    };
+}
+
+// ECMAScript `Number.prototype.toString`, as required by RFC 8785, for finite `v`
+fn write_es_number(out: &mut String, v: f64) {
+    if v == 0.0 {
+        out.push('0');
+        return;
+    }
+    if v < 0.0 {
+        out.push('-');
+    }
+    // Rust's `{:e}` yields the shortest round-tripping digits, e.g. `1.2345e-7`
+    let sci = format!("{:e}", v.abs());
+    let (mantissa, exp) = sci.split_once('e').unwrap_or((&sci, "0"));
+    let digits = mantissa.replace('.', "");
+    let k = digits.len() as i32;
+    // the value is `0.digits * 10^n`
+    let n = exp.parse::<i32>().unwrap_or_default() + 1;
+    if k <= n && n <= 21 {
+        out.push_str(&digits);
+        out.push_str(&"0".repeat((n - k) as usize));
+    } else if 0 < n && n <= 21 {
+        out.push_str(&digits[..n as usize]);
+        out.push('.');
+        out.push_str(&digits[n as usize..]);
+    } else if -6 < n && n <= 0 {
+        out.push_str("0.");
+        out.push_str(&"0".repeat(-n as usize));
+        out.push_str(&digits);
+    } else {
+        out.push_str(&digits[..1]);
+        if k > 1 {
+            out.push('.');
+            out.push_str(&digits[1..]);
+        }
+        let _ = write!(out, "e{}{}", if n > 0 { "+" } else { "-" }, (n - 1).abs());
+    }
+}
+
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NumberFormat;
+    use crate::json::{winnow_parse, JsonValue, Num};
+
+    #[test]
+    fn to_pretty_sorted_should_work() -> anyhow::Result<()> {
+        let value = winnow_parse(
+            r#"{"name": "John \"JD\" Doe", "age": 43, "marks": [87.5, 90], "address": {"zip": 10001, "city": "New York"}, "is_student": false}"#,
+        )?;
+        let expected = r#"{
+  "address": {
+    "city": "New York",
+    "zip": 10001
+  },
+  "age": 43,
+  "is_student": false,
+  "marks": [
+    87.5,
+    90
+  ],
+  "name": "John \"JD\" Doe"
+}"#;
+        assert_eq!(value.to_pretty_sorted(2), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn to_sorted_compact_string_should_work() -> anyhow::Result<()> {
+        let value = winnow_parse(r#"{ "b": [1, {"d": null, "c": "x"}], "a": true }"#)?;
+        assert_eq!(
+            value.to_sorted_compact_string(),
+            r#"{"a":true,"b":[1,{"c":"x","d":null}]}"#
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn to_canonical_json_should_sort_keys_by_utf16() -> anyhow::Result<()> {
+        // from RFC 8785 section 3.2.3
+        let value = winnow_parse(
+            r#"{
+                "\u20ac": "Euro Sign",
+                "\r": "Carriage Return",
+                "\ufb33": "Hebrew Letter Dalet With Dagesh",
+                "1": "One",
+                "\ud83d\ude00": "Emoji: Grinning Face",
+                "\u0080": "Control",
+                "\u00f6": "Latin Small Letter O With Diaeresis"
+            }"#,
+        )?;
+        let expected = "{\"\\r\":\"Carriage Return\",\"1\":\"One\",\"\u{80}\":\"Control\",\"\u{f6}\":\"Latin Small Letter O With Diaeresis\",\"\u{20ac}\":\"Euro Sign\",\"\u{1f600}\":\"Emoji: Grinning Face\",\"\u{fb33}\":\"Hebrew Letter Dalet With Dagesh\"}";
+        assert_eq!(value.to_canonical_json()?, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn to_canonical_json_should_format_numbers() -> anyhow::Result<()> {
+        let cases = [
+            (333_333_333.333_333_3, "333333333.3333333"),
+            (1e30, "1e+30"),
+            (4.50, "4.5"),
+            (2e-3, "0.002"),
+            (0.000000000000000000000000001, "1e-27"),
+            (1e21, "1e+21"),
+            (1e20, "100000000000000000000"),
+            (-1.5e-7, "-1.5e-7"),
+            (-0.0, "0"),
+        ];
+        for (v, expected) in cases {
+            let value = JsonValue::Number(Num::Float(v));
+            assert_eq!(value.to_canonical_json()?, expected);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn to_canonical_json_should_reject_non_finite_numbers() {
+        let value = JsonValue::array([
+            JsonValue::Number(Num::Int(10)),
+            JsonValue::object([("x".to_string(), JsonValue::Number(Num::Float(f64::NAN)))]),
+        ]);
+        let err = value.to_canonical_json().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"can't canonicalize a non-finite number at "/1/x""#
+        );
+        let value = JsonValue::Number(Num::Float(f64::NEG_INFINITY));
+        assert!(value.to_canonical_json().is_err());
+    }
+
+    #[test]
+    fn to_string_with_number_format_should_work() {
+        let value = JsonValue::array([
+            JsonValue::Number(Num::Float(1.0)),
+            JsonValue::Number(Num::Int(1)),
+        ]);
+        assert_eq!(value.to_string_with(None, NumberFormat::default()), "[1,1]");
+        let numbers = NumberFormat {
+            float_trailing_zero: true,
+            ..Default::default()
+        };
+        assert_eq!(value.to_string_with(None, numbers), "[1.0,1]");
+
+        let value = JsonValue::array([
+            JsonValue::Number(Num::Float(2.0 / 3.0)),
+            JsonValue::Number(Num::Float(1.5e21)),
+            JsonValue::Number(Num::Float(-3.0)),
+        ]);
+        let numbers = NumberFormat {
+            float_trailing_zero: true,
+            precision: Some(2),
+            exponent_threshold: Some(1e21),
+        };
+        assert_eq!(
+            value.to_string_with(Some(1), numbers),
+            "[\n 0.67,\n 1.50e21,\n -3.0\n]"
+        );
+    }
+
+    #[test]
+    fn non_finite_numbers_should_be_written_as_null() {
+        let value = JsonValue::Number(Num::Float(f64::NAN));
+        assert_eq!(value.to_pretty_sorted(2), "null");
+
+        let value = JsonValue::array([
+            JsonValue::Number(Num::Float(f64::NAN)),
+            JsonValue::Number(Num::Float(f64::INFINITY)),
+            JsonValue::Number(Num::Float(f64::NEG_INFINITY)),
+        ]);
+        assert_eq!(value.to_pretty_sorted(2), "[\n  null,\n  null,\n  null\n]");
+        let numbers = NumberFormat {
+            precision: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(value.to_string_with(None, numbers), "[null,null,null]");
+        let numbers = NumberFormat {
+            float_trailing_zero: true,
+            precision: Some(2),
+            exponent_threshold: Some(1e21),
+        };
+        assert_eq!(value.to_string_with(None, numbers), "[null,null,null]");
+    }
+}
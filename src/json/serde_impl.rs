@@ -0,0 +1,130 @@
+use std::{collections::HashMap, fmt};
+
+use serde::{
+    de::{self, MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use super::{JsonValue, Num};
+
+impl Serialize for JsonValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            JsonValue::Null => serializer.serialize_unit(),
+            JsonValue::Bool(b) => serializer.serialize_bool(*b),
+            JsonValue::Number(Num::Int(v)) => serializer.serialize_i64(*v),
+            JsonValue::Number(Num::Float(v)) => serializer.serialize_f64(*v),
+            JsonValue::String(s) => serializer.serialize_str(s),
+            JsonValue::Array(arr) => serializer.collect_seq(arr),
+            JsonValue::Object(obj) => serializer.collect_map(obj),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for JsonValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(JsonValueVisitor)
+    }
+}
+
+struct JsonValueVisitor;
+
+impl<'de> Visitor<'de> for JsonValueVisitor {
+    type Value = JsonValue;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any JSON value")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<JsonValue, E> {
+        Ok(JsonValue::Null)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<JsonValue, E> {
+        Ok(JsonValue::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<JsonValue, D::Error> {
+        JsonValue::deserialize(deserializer)
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<JsonValue, E> {
+        Ok(JsonValue::Bool(v))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<JsonValue, E> {
+        Ok(JsonValue::Number(Num::Int(v)))
+    }
+
+    // integers beyond i64 can only be kept approximately
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<JsonValue, E> {
+        Ok(JsonValue::Number(match i64::try_from(v) {
+            Ok(v) => Num::Int(v),
+            Err(_) => Num::Float(v as f64),
+        }))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<JsonValue, E> {
+        Ok(JsonValue::Number(Num::Float(v)))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<JsonValue, E> {
+        Ok(JsonValue::String(v.to_string()))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<JsonValue, E> {
+        Ok(JsonValue::String(v))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<JsonValue, A::Error> {
+        let mut arr = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(v) = seq.next_element()? {
+            arr.push(v);
+        }
+        Ok(JsonValue::Array(arr))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<JsonValue, A::Error> {
+        let mut obj = HashMap::with_capacity(map.size_hint().unwrap_or(0));
+        while let Some((k, v)) = map.next_entry()? {
+            obj.insert(k, v);
+        }
+        Ok(JsonValue::Object(obj))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use crate::json::{winnow_parse, JsonValue, Num};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Wrapper {
+        id: u32,
+        data: JsonValue,
+    }
+
+    #[test]
+    fn serde_round_trip_should_work() -> anyhow::Result<()> {
+        let data = winnow_parse(r#"{"count": 3, "ratio": 3.0, "tags": ["a", null, true]}"#)?;
+        let wrapper = Wrapper { id: 1, data };
+
+        let s = serde_json::to_string(&wrapper)?;
+        assert!(s.contains(r#""count":3"#));
+        assert!(s.contains(r#""ratio":3.0"#));
+
+        let ret: Wrapper = serde_json::from_str(&s)?;
+        assert_eq!(ret, wrapper);
+        assert_eq!(
+            ret.data.pointer("/count"),
+            Some(&JsonValue::Number(Num::Int(3)))
+        );
+        assert_eq!(
+            ret.data.pointer("/ratio"),
+            Some(&JsonValue::Number(Num::Float(3.0)))
+        );
+
+        Ok(())
+    }
+}
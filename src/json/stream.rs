@@ -0,0 +1,132 @@
+use std::io::{BufReader, Read};
+
+use anyhow::{anyhow, bail, Result};
+
+use super::{winnow_parse, JsonValue};
+
+/// Parse a top-level JSON array from `reader`, handing each element to `on_item` as soon as
+/// it is complete. Only one element is held in memory at a time, so arbitrarily large
+/// arrays can be processed. An error from `on_item` stops the stream and is returned.
+pub fn stream_array<R: Read>(
+    reader: R,
+    mut on_item: impl FnMut(JsonValue) -> Result<()>,
+) -> Result<()> {
+    let mut bytes = BufReader::new(reader).bytes();
+    let mut next = move || bytes.next().transpose().map_err(anyhow::Error::from);
+
+    match skip_whitespace(&mut next)? {
+        Some(b'[') => {}
+        _ => bail!("expected '[' at the start of the stream"),
+    }
+    let mut count = 0;
+    let mut item = Vec::new();
+    loop {
+        item.clear();
+        let end = scan_item(&mut next, &mut item)?;
+        let text = std::str::from_utf8(&item)?.trim();
+        if text.is_empty() {
+            // only `[]` may close without a value
+            if end == b']' && count == 0 {
+                break;
+            }
+            bail!("expected a value after element {}", count);
+        }
+        let value = winnow_parse(text).map_err(|e| anyhow!("element {}: {}", count, e))?;
+        on_item(value)?;
+        count += 1;
+        if end == b']' {
+            break;
+        }
+    }
+    if skip_whitespace(&mut next)?.is_some() {
+        bail!("unexpected data after the closing ']'");
+    }
+    Ok(())
+}
+
+fn skip_whitespace(next: &mut impl FnMut() -> Result<Option<u8>>) -> Result<Option<u8>> {
+    loop {
+        match next()? {
+            Some(b) if b.is_ascii_whitespace() => {}
+            other => return Ok(other),
+        }
+    }
+}
+
+// copy the raw bytes of one element into `item`, returning the `,` or `]` that ended it
+fn scan_item(next: &mut impl FnMut() -> Result<Option<u8>>, item: &mut Vec<u8>) -> Result<u8> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    loop {
+        let Some(b) = next()? else {
+            bail!("unexpected end of input inside the array");
+        };
+        if in_string {
+            match b {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+        } else {
+            match b {
+                b',' | b']' if depth == 0 => return Ok(b),
+                b'"' => in_string = true,
+                b'[' | b'{' => depth += 1,
+                b']' | b'}' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+        item.push(b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::Num;
+
+    #[test]
+    fn stream_array_should_work() -> Result<()> {
+        let items: Vec<String> = (0..1000)
+            .map(|i| format!(r#"{{"id": {}, "name": "item, [{}]"}}"#, i, i))
+            .collect();
+        let input = format!("[\n{}\n]\n", items.join(",\n"));
+
+        let mut count = 0;
+        stream_array(input.as_bytes(), |item| {
+            assert_eq!(
+                item.pointer("/id"),
+                Some(&JsonValue::Number(Num::Int(count)))
+            );
+            count += 1;
+            Ok(())
+        })?;
+        assert_eq!(count, 1000);
+
+        Ok(())
+    }
+
+    #[test]
+    fn stream_array_should_report_errors() {
+        let noop = |_| Ok(());
+        assert!(stream_array("[]".as_bytes(), noop).is_ok());
+        assert!(stream_array(r#"{"a": 1}"#.as_bytes(), noop).is_err());
+        assert!(stream_array("[1, 2,]".as_bytes(), noop).is_err());
+        assert!(stream_array("[1, 2".as_bytes(), noop).is_err());
+        assert!(stream_array("[1, 2] 3".as_bytes(), noop).is_err());
+        assert!(stream_array("[1, @]".as_bytes(), noop).is_err());
+
+        let mut seen = 0;
+        let ret = stream_array("[1, 2, 3]".as_bytes(), |_| {
+            seen += 1;
+            if seen == 2 {
+                bail!("stop");
+            }
+            Ok(())
+        });
+        assert_eq!(ret.unwrap_err().to_string(), "stop");
+        assert_eq!(seen, 2);
+    }
+}
@@ -7,9 +7,8 @@ use std::collections::HashMap;
 #[grammar = "json.pest"]
 struct JsonParser;
 
-#[allow(unused)]
 #[derive(Debug, PartialEq)]
-enum JsonValue {
+pub enum JsonValue {
     Null,
     Bool(bool),
     Number(f64),
@@ -18,25 +17,12 @@ enum JsonValue {
     Object(HashMap<String, JsonValue>),
 }
 
-fn main() -> Result<()> {
-    let s = r#"{
-        "name": "John Doe",
-        "age": 43,
-        "is_student": false,
-        "marks": [87.0, 90, -45.7, 67.9],
-        "address": {
-            "city": "New York",
-            "zip": 10001
-        }
-    }"#;
-
-    let parsed = JsonParser::parse(Rule::json, s)?
+/// Parse a JSON document with the pest backend.
+pub fn pest_parse(input: &str) -> Result<JsonValue> {
+    let parsed = JsonParser::parse(Rule::json, input)?
         .next()
         .ok_or_else(|| anyhow!("json has no value"))?;
-    let value = parse_value(parsed)?;
-    println!("{:#?}", value);
-
-    Ok(())
+    parse_value(parsed)
 }
 
 fn parse_array(pair: Pair<Rule>) -> Result<Vec<JsonValue>> {
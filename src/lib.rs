@@ -0,0 +1,42 @@
+pub mod ansi;
+pub mod app_log;
+pub mod cef;
+pub mod cron;
+pub mod diff;
+pub mod dns;
+pub mod dsn;
+pub mod email;
+pub mod form;
+pub mod geojson;
+pub mod glob;
+pub mod graph;
+pub mod haproxy_log;
+pub mod http;
+pub mod ical;
+pub mod ignore;
+pub mod image;
+pub mod irc;
+pub mod journal;
+pub mod json;
+pub mod json2;
+pub mod logfmt;
+pub mod ls;
+pub mod mac;
+pub mod nginx_log;
+pub mod parsers;
+pub mod ping;
+pub mod prom;
+pub mod properties;
+pub mod quantity;
+pub mod reg;
+pub mod resp;
+pub mod robots;
+pub mod semver;
+pub mod shell;
+pub mod smtp;
+pub mod socket;
+pub mod table;
+pub mod tcpdump;
+pub mod tsv;
+pub mod user_agent;
+pub mod vars;
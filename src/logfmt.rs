@@ -0,0 +1,62 @@
+use winnow::{
+    ascii::{space0, space1},
+    combinator::{alt, delimited, eof, opt, preceded, separated},
+    token::take_till,
+    PResult, Parser,
+};
+
+use crate::parsers::quoted;
+
+// need to parse:
+// level=info msg="hello world" dur=1.5 debug
+pub fn parse_logfmt(s: &str) -> PResult<Vec<(String, Option<String>)>> {
+    let input = &mut s.trim_end_matches(['\r', '\n']);
+    delimited(space0, separated(0.., parse_pair, space1), (space0, eof)).parse_next(input)
+}
+
+// a bare key without `=` has no value, `key=` has an empty one
+fn parse_pair(s: &mut &str) -> PResult<(String, Option<String>)> {
+    let key = take_till(1.., [' ', '\t', '=', '"']).parse_next(s)?;
+    let value = alt((
+        quoted('"', true),
+        take_till(0.., [' ', '\t'])
+            .verify(|v: &str| !v.starts_with('"'))
+            .map(|v: &str| v.to_string()),
+    ));
+    let value = opt(preceded('=', value)).parse_next(s)?;
+    Ok((key.to_string(), value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_logfmt_should_work() {
+        let ret = parse_logfmt(r#"level=info msg="hello \"world\"" dur=1.5"#).unwrap();
+        assert_eq!(
+            ret,
+            vec![
+                ("level".to_string(), Some("info".to_string())),
+                ("msg".to_string(), Some("hello \"world\"".to_string())),
+                ("dur".to_string(), Some("1.5".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_logfmt_with_bare_key_should_work() {
+        let ret = parse_logfmt("  debug  path=/api empty= retry\n").unwrap();
+        assert_eq!(
+            ret,
+            vec![
+                ("debug".to_string(), None),
+                ("path".to_string(), Some("/api".to_string())),
+                ("empty".to_string(), Some("".to_string())),
+                ("retry".to_string(), None),
+            ]
+        );
+        assert_eq!(parse_logfmt("").unwrap(), vec![]);
+        assert!(parse_logfmt(r#"msg="unterminated"#).is_err());
+    }
+}
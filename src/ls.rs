@@ -0,0 +1,110 @@
+use winnow::{
+    ascii::{digit1, space1},
+    combinator::{opt, rest, terminated},
+    token::{one_of, take_till, take_while},
+    PResult, Parser,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct LsEntry {
+    pub perms: String,
+    pub links: u32,
+    pub owner: String,
+    pub group: String,
+    pub size: u64,
+    pub mtime: String,
+    pub name: String,
+}
+
+// need to parse:
+// -rw-r--r-- 1 user group 1024 Oct 11 22:14 file.txt
+pub fn parse_ls_long(line: &str) -> PResult<LsEntry> {
+    let input = &mut line.trim_end_matches(['\r', '\n']);
+    let perms = terminated(parse_perms, space1).parse_next(input)?;
+    let links = terminated(digit1.parse_to(), space1).parse_next(input)?;
+    let owner = terminated(parse_field, space1).parse_next(input)?;
+    let group = terminated(parse_field, space1).parse_next(input)?;
+    let size = terminated(digit1.parse_to(), space1).parse_next(input)?;
+    let mtime = terminated(parse_mtime, space1).parse_next(input)?;
+    // the name runs to the end of the line, spaces and symlink targets included
+    let name = rest
+        .verify(|name: &str| !name.is_empty())
+        .parse_next(input)?;
+    Ok(LsEntry {
+        perms: perms.to_string(),
+        links,
+        owner: owner.to_string(),
+        group: group.to_string(),
+        size,
+        mtime: mtime.to_string(),
+        name: name.to_string(),
+    })
+}
+
+// file type followed by three rwx triplets, optionally marked with `@`, `+` or `.`
+fn parse_perms<'a>(s: &mut &'a str) -> PResult<&'a str> {
+    (
+        one_of(['-', 'd', 'l', 'c', 'b', 'p', 's']),
+        take_while(9, ['-', 'r', 'w', 'x', 's', 'S', 't', 'T']),
+        opt(one_of(['@', '+', '.'])),
+    )
+        .take()
+        .parse_next(s)
+}
+
+fn parse_field<'a>(s: &mut &'a str) -> PResult<&'a str> {
+    take_till(1.., |c: char| c.is_whitespace()).parse_next(s)
+}
+
+// `Oct 11 22:14` for recent files or `Oct 11  2023` for older ones
+fn parse_mtime<'a>(s: &mut &'a str) -> PResult<&'a str> {
+    (
+        take_while(3, |c: char| c.is_ascii_alphabetic()),
+        space1,
+        digit1,
+        space1,
+        take_while(4..=5, |c: char| c.is_ascii_digit() || c == ':'),
+    )
+        .take()
+        .parse_next(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ls_long_file_should_work() {
+        let ret = parse_ls_long("-rw-r--r-- 1 user group 1024 Oct 11 22:14 my file.txt").unwrap();
+        assert_eq!(
+            ret,
+            LsEntry {
+                perms: "-rw-r--r--".to_string(),
+                links: 1,
+                owner: "user".to_string(),
+                group: "group".to_string(),
+                size: 1024,
+                mtime: "Oct 11 22:14".to_string(),
+                name: "my file.txt".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_ls_long_directory_should_work() {
+        let ret =
+            parse_ls_long("drwxr-xr-x@  12 root  wheel   384 Jan  3  2023 Applications").unwrap();
+        assert_eq!(ret.perms, "drwxr-xr-x@");
+        assert_eq!(ret.links, 12);
+        assert_eq!(ret.owner, "root");
+        assert_eq!(ret.size, 384);
+        assert_eq!(ret.mtime, "Jan  3  2023");
+        assert_eq!(ret.name, "Applications");
+
+        let ret = parse_ls_long("lrwxrwxrwx 1 u g 7 Oct 11 22:14 link -> target").unwrap();
+        assert_eq!(ret.name, "link -> target");
+
+        assert!(parse_ls_long("xrw-r--r-- 1 user group 1024 Oct 11 22:14 file").is_err());
+        assert!(parse_ls_long("-rw-r--r-- 1 user group 1024 Oct 11 22:14 ").is_err());
+    }
+}
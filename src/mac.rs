@@ -0,0 +1,48 @@
+use winnow::{
+    combinator::{alt, eof, peek, preceded, repeat, terminated},
+    token::take_while,
+    PResult, Parser,
+};
+
+// need to parse:
+// 00:1a:2b:3c:4d:5e or 00-1A-2B-3C-4D-5E
+pub fn parse_mac(s: &str) -> PResult<[u8; 6]> {
+    let input = &mut (&*s);
+    let first = parse_octet(input)?;
+    // the first separator decides which one the remaining octets must use
+    let sep = peek(alt((':', '-'))).parse_next(input)?;
+    let rest: Vec<u8> = terminated(repeat(5, preceded(sep, parse_octet)), eof).parse_next(input)?;
+    Ok([first, rest[0], rest[1], rest[2], rest[3], rest[4]])
+}
+
+fn parse_octet(s: &mut &str) -> PResult<u8> {
+    take_while(2, |c: char| c.is_ascii_hexdigit())
+        .try_map(|hex| u8::from_str_radix(hex, 16))
+        .parse_next(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_mac_with_colons_should_work() {
+        let ret = parse_mac("00:1a:2b:3c:4d:5e").unwrap();
+        assert_eq!(ret, [0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0x5e]);
+    }
+
+    #[test]
+    fn parse_mac_with_hyphens_should_work() {
+        let ret = parse_mac("00-1A-2B-3C-4D-5e").unwrap();
+        assert_eq!(ret, [0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0x5e]);
+    }
+
+    #[test]
+    fn parse_invalid_mac_should_fail() {
+        assert!(parse_mac("00:1a:2b:3c:4d").is_err());
+        assert!(parse_mac("00:1a:2b:3c:4d:5e:6f").is_err());
+        assert!(parse_mac("00:1a-2b:3c:4d:5e").is_err());
+        assert!(parse_mac("00:1a:2b:3c:4d:5").is_err());
+        assert!(parse_mac("00:1a:2b:3c:4d:5g").is_err());
+    }
+}
@@ -0,0 +1,531 @@
+use std::{
+    fmt,
+    io::{BufRead, BufReader, Lines, Read},
+    net::{IpAddr, Ipv4Addr},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, FixedOffset, Utc};
+use winnow::{
+    ascii::{digit1, space0},
+    combinator::{alt, delimited, dispatch, fail, peek, separated, terminated},
+    token::{any, take_till, take_until},
+    PResult, Parser,
+};
+
+use crate::parsers::{line_column, quoted};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum HttpMethod {
+    Get,
+    Post,
+    Put,
+    Delete,
+    Head,
+    Options,
+    Connect,
+    Trace,
+    Patch,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum HttpProtocol {
+    HTTP1_0,
+    HTTP1_1,
+    HTTP2_0,
+    HTTP3_0,
+}
+
+#[derive(Debug)]
+pub struct NginxLog {
+    pub addr: IpAddr,
+    pub date_time: DateTime<Utc>,
+    pub method: HttpMethod,
+    pub url: String,
+    pub protocol: HttpProtocol,
+    pub status: u16,
+    pub body_bytes: u64,
+    pub referer: String,
+    pub user_agent: String,
+}
+
+// need to parse:
+// 93.180.71.3 - - [17/May/2015:08:05:32 +0000] "GET /downloads/product_1 HTTP/1.1" 304 0 "-" "Debian APT-HTTP/1.3 (0.8.16~exp12ubuntu10.21)"
+// with winnow parser combinator
+pub fn parse_nginx_log(s: &str) -> PResult<NginxLog> {
+    parse_record(&mut &*s)
+}
+
+/// Parse line number `line` of a log, reporting the line and column where it's malformed.
+pub fn parse_nginx_line(s: &str, line: usize) -> Result<NginxLog> {
+    let input = &mut (&*s);
+    parse_record(input).map_err(|e| {
+        let (_, column) = line_column(s, s.len() - input.len());
+        anyhow!(
+            "Failed to parse log at line {}, column {}: {:?}",
+            line,
+            column,
+            e
+        )
+    })
+}
+
+fn parse_record(input: &mut &str) -> PResult<NginxLog> {
+    let ip = parse_ip(input)?;
+    parse_ignored(input)?;
+    parse_ignored(input)?;
+    let date_time = parse_date_time(input)?;
+    let (method, url, protocol) = parse_http(input)?;
+    let status = parse_status(input)?;
+    let body_bytes = parse_bytes(input)?;
+    let referer = parse_quoted_string(input)?;
+    let user_agent = parse_quoted_string(input)?;
+    Ok(NginxLog {
+        addr: ip,
+        date_time,
+        method,
+        url,
+        protocol,
+        status,
+        body_bytes,
+        referer,
+        user_agent,
+    })
+}
+
+/// Parse one record per line, skipping blank lines and lines starting with `#`.
+pub fn parse_nginx_logs(s: &str) -> PResult<Vec<NginxLog>> {
+    s.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(parse_nginx_log)
+        .collect()
+}
+
+/// Lazily parses nginx log records from a reader, one line at a time.
+///
+/// Blank and `#` comment lines are skipped like in [`parse_nginx_logs`]. Malformed lines are
+/// yielded as errors unless `skip_errors` is set, IO errors are always yielded.
+pub struct NginxLogStream<R> {
+    lines: Lines<BufReader<R>>,
+    line: usize,
+    skip_errors: bool,
+}
+
+impl<R: Read> NginxLogStream<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            lines: BufReader::new(reader).lines(),
+            line: 0,
+            skip_errors: false,
+        }
+    }
+
+    /// Drop malformed lines instead of yielding them as errors.
+    pub fn skip_errors(mut self, skip_errors: bool) -> Self {
+        self.skip_errors = skip_errors;
+        self
+    }
+}
+
+impl<R: Read> Iterator for NginxLogStream<R> {
+    type Item = Result<NginxLog>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e.into())),
+            };
+            self.line += 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match parse_nginx_line(line, self.line) {
+                Ok(log) => return Some(Ok(log)),
+                Err(_) if self.skip_errors => continue,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+/// Render `logs` as CSV with a header row and one column per field in `fields`.
+///
+/// Supported fields are `ip`, `status`, `bytes`, `method`, `url` and `time` (RFC 3339),
+/// unknown fields produce empty cells.
+pub fn logs_to_csv(logs: &[NginxLog], fields: &[&str]) -> String {
+    let mut out = fields
+        .iter()
+        .map(|f| csv_escape(f))
+        .collect::<Vec<_>>()
+        .join(",");
+    out.push('\n');
+    for log in logs {
+        let row: Vec<String> = fields
+            .iter()
+            .map(|field| {
+                let value = match *field {
+                    "ip" => log.addr.to_string(),
+                    "status" => log.status.to_string(),
+                    "bytes" => log.body_bytes.to_string(),
+                    "method" => log.method.to_string(),
+                    "url" => log.url.clone(),
+                    "time" => log.date_time.to_rfc3339(),
+                    _ => String::new(),
+                };
+                csv_escape(&value)
+            })
+            .collect();
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+// quote values containing separators, quotes or newlines, doubling any inner quotes
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub(crate) fn parse_ip(s: &mut &str) -> PResult<IpAddr> {
+    let ret: Vec<u8> = separated(4, digit1.parse_to::<u8>(), '.').parse_next(s)?;
+    space0(s)?;
+    Ok(IpAddr::V4(Ipv4Addr::new(ret[0], ret[1], ret[2], ret[3])))
+}
+
+fn parse_ignored(s: &mut &str) -> PResult<()> {
+    "- ".parse_next(s)?;
+    Ok(())
+}
+
+// supports both the default `[$time_local]` format and `$time_iso8601`
+fn parse_date_time(s: &mut &str) -> PResult<DateTime<Utc>> {
+    let ret = dispatch! {peek(any);
+        '[' => parse_local_date_time,
+        '0'..='9' => parse_iso8601_date_time,
+        _ => fail,
+    }
+    .parse_next(s)?;
+    space0(s)?;
+    Ok(ret.with_timezone(&Utc))
+}
+
+fn parse_local_date_time(s: &mut &str) -> PResult<DateTime<FixedOffset>> {
+    delimited('[', take_until(1.., ']'), ']')
+        .try_map(|dt| DateTime::parse_from_str(dt, "%d/%b/%Y:%H:%M:%S %z"))
+        .parse_next(s)
+}
+
+fn parse_iso8601_date_time(s: &mut &str) -> PResult<DateTime<FixedOffset>> {
+    take_till(1.., ' ')
+        .try_map(DateTime::parse_from_rfc3339)
+        .parse_next(s)
+}
+
+fn parse_http(s: &mut &str) -> PResult<(HttpMethod, String, HttpProtocol)> {
+    let protocol = terminated(parse_protocol, space0);
+    let ret = delimited('"', (parse_method, parse_url, protocol), '"').parse_next(s)?;
+    space0(s)?;
+    Ok(ret)
+}
+
+pub(crate) fn parse_method(s: &mut &str) -> PResult<HttpMethod> {
+    let ret = alt((
+        "GET", "POST", "PUT", "DELETE", "HEAD", "OPTIONS", "CONNECT", "TRACE", "PATCH",
+    ))
+    .parse_to()
+    .parse_next(s)?;
+    space0(s)?;
+    Ok(ret)
+}
+
+pub(crate) fn parse_url(s: &mut &str) -> PResult<String> {
+    let ret = take_until(1.., ' ').parse_next(s)?;
+    space0(s)?;
+    Ok(ret.to_string())
+}
+
+// unlike the other request fields this leaves trailing whitespace to the caller
+pub(crate) fn parse_protocol(s: &mut &str) -> PResult<HttpProtocol> {
+    alt(("HTTP/1.0", "HTTP/1.1", "HTTP/2.0", "HTTP/3.0"))
+        .parse_to()
+        .parse_next(s)
+}
+
+fn parse_status(s: &mut &str) -> PResult<u16> {
+    let ret = digit1.parse_to::<u16>().parse_next(s)?;
+    space0(s)?;
+    Ok(ret)
+}
+
+fn parse_bytes(s: &mut &str) -> PResult<u64> {
+    let ret = digit1.parse_to::<u64>().parse_next(s)?;
+    space0(s)?;
+    Ok(ret)
+}
+
+// nginx logs `-` for a missing value, so an empty field is malformed
+fn parse_quoted_string(s: &mut &str) -> PResult<String> {
+    let ret = quoted('"', false)
+        .verify(|s: &String| !s.is_empty())
+        .parse_next(s)?;
+    space0(s)?;
+    Ok(ret)
+}
+
+impl FromStr for HttpProtocol {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "HTTP/1.0" => Ok(HttpProtocol::HTTP1_0),
+            "HTTP/1.1" => Ok(HttpProtocol::HTTP1_1),
+            "HTTP/2.0" => Ok(HttpProtocol::HTTP2_0),
+            "HTTP/3.0" => Ok(HttpProtocol::HTTP3_0),
+            _ => Err(anyhow!("Invalid HttpProtocol")),
+        }
+    }
+}
+
+impl FromStr for HttpMethod {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "GET" => Ok(HttpMethod::Get),
+            "POST" => Ok(HttpMethod::Post),
+            "PUT" => Ok(HttpMethod::Put),
+            "DELETE" => Ok(HttpMethod::Delete),
+            "HEAD" => Ok(HttpMethod::Head),
+            "OPTIONS" => Ok(HttpMethod::Options),
+            "CONNECT" => Ok(HttpMethod::Connect),
+            "TRACE" => Ok(HttpMethod::Trace),
+            "PATCH" => Ok(HttpMethod::Patch),
+            _ => Err(anyhow!("Invalid HttpMethod")),
+        }
+    }
+}
+
+impl TryFrom<&str> for HttpProtocol {
+    type Error = anyhow::Error;
+
+    fn try_from(s: &str) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl TryFrom<&str> for HttpMethod {
+    type Error = anyhow::Error;
+
+    fn try_from(s: &str) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl fmt::Display for HttpProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            HttpProtocol::HTTP1_0 => "HTTP/1.0",
+            HttpProtocol::HTTP1_1 => "HTTP/1.1",
+            HttpProtocol::HTTP2_0 => "HTTP/2.0",
+            HttpProtocol::HTTP3_0 => "HTTP/3.0",
+        };
+        f.write_str(s)
+    }
+}
+
+impl fmt::Display for HttpMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            HttpMethod::Get => "GET",
+            HttpMethod::Post => "POST",
+            HttpMethod::Put => "PUT",
+            HttpMethod::Delete => "DELETE",
+            HttpMethod::Head => "HEAD",
+            HttpMethod::Options => "OPTIONS",
+            HttpMethod::Connect => "CONNECT",
+            HttpMethod::Trace => "TRACE",
+            HttpMethod::Patch => "PATCH",
+        };
+        f.write_str(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ip_should_work() -> Result<()> {
+        let mut s = "1.1.1.1";
+        let ip = parse_ip(&mut s).unwrap();
+
+        assert_eq!(s, "");
+        assert_eq!(ip, IpAddr::V4(Ipv4Addr::new(1, 1, 1, 1)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_date_time_should_work() -> Result<()> {
+        let mut s = "[17/May/2015:08:05:32 +0000]";
+        let dt = parse_date_time(&mut s).unwrap();
+
+        assert_eq!(s, "");
+        assert_eq!(
+            dt,
+            DateTime::parse_from_str("17/May/2015:08:05:32 +0000", "%d/%b/%Y:%H:%M:%S %z")
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_iso8601_date_time_should_work() -> Result<()> {
+        let mut s = "[17/May/2015:08:05:32 +0000]";
+        let local = parse_date_time(&mut s).unwrap();
+
+        let mut s = "2015-05-17T08:05:32+00:00";
+        let iso = parse_date_time(&mut s).unwrap();
+
+        assert_eq!(s, "");
+        assert_eq!(local, iso);
+
+        let mut s = "2015-05-17T10:05:32+02:00 \"GET";
+        let iso = parse_date_time(&mut s).unwrap();
+
+        assert_eq!(s, "\"GET");
+        assert_eq!(local, iso);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_invalid_date_time_should_fail() -> Result<()> {
+        let mut s = "[17/Foo/2015:08:05:32 +0000]";
+        assert!(parse_date_time(&mut s).is_err());
+
+        let mut s = "May 17 2015";
+        assert!(parse_date_time(&mut s).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_http_should_work() -> Result<()> {
+        let mut s = "\"GET /downloads/product_1 HTTP/1.1\"";
+        let (method, url, protocol) = parse_http(&mut s).unwrap();
+
+        assert_eq!(s, "");
+        assert_eq!(method, HttpMethod::Get);
+        assert_eq!(url, "/downloads/product_1");
+        assert_eq!(protocol, HttpProtocol::HTTP1_1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_http_method_display_should_round_trip() -> Result<()> {
+        assert_eq!(HttpMethod::Get.to_string(), "GET");
+        for method in [
+            "GET", "POST", "PUT", "DELETE", "HEAD", "OPTIONS", "CONNECT", "TRACE", "PATCH",
+        ] {
+            let parsed: HttpMethod = method.parse()?;
+            assert_eq!(parsed.to_string(), method);
+            assert_eq!(HttpMethod::try_from(method)?, parsed);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_http_protocol_display_should_round_trip() -> Result<()> {
+        assert_eq!(HttpProtocol::HTTP1_1.to_string(), "HTTP/1.1");
+        for protocol in ["HTTP/1.0", "HTTP/1.1", "HTTP/2.0", "HTTP/3.0"] {
+            let parsed: HttpProtocol = protocol.parse()?;
+            assert_eq!(parsed.to_string(), protocol);
+            assert_eq!(HttpProtocol::try_from(protocol)?, parsed);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_nginx_log_with_empty_field_should_fail() {
+        let s = r#"93.180.71.3 - - [17/May/2015:08:05:32 +0000] "GET /downloads/product_1 HTTP/1.1" 304 0 "" "Debian APT-HTTP/1.3""#;
+        assert!(parse_nginx_log(s).is_err());
+    }
+
+    #[test]
+    fn test_parse_nginx_logs_should_skip_comments_and_blanks() -> Result<()> {
+        let s = r#"# sample records
+93.180.71.3 - - [17/May/2015:08:05:32 +0000] "GET /downloads/product_1 HTTP/1.1" 304 0 "-" "Debian APT-HTTP/1.3 (0.8.16~exp12ubuntu10.21)"
+
+  # indented comment
+80.91.33.133 - - [17/May/2015:08:05:24 +0000] "GET /downloads/product_2 HTTP/1.1" 200 490 "-" "Debian APT-HTTP/1.3 (0.8.16~exp12ubuntu10.17)"
+
+"#;
+        let logs = parse_nginx_logs(s).unwrap();
+
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[0].url, "/downloads/product_1");
+        assert_eq!(logs[1].status, 200);
+
+        assert!(parse_nginx_logs("# only a comment\nnot a log line").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_logs_to_csv_should_work() -> Result<()> {
+        let s = r#"93.180.71.3 - - [17/May/2015:08:05:32 +0000] "GET /downloads/product_1 HTTP/1.1" 304 0 "-" "Debian APT-HTTP/1.3 (0.8.16~exp12ubuntu10.21)"
+80.91.33.133 - - [17/May/2015:08:05:24 +0000] "GET /search?q=a,b HTTP/1.1" 200 490 "-" "Debian APT-HTTP/1.3 (0.8.16~exp12ubuntu10.17)""#;
+        let logs = parse_nginx_logs(s).unwrap();
+
+        assert_eq!(
+            logs_to_csv(&logs, &["ip", "status", "bytes"]),
+            "ip,status,bytes\n93.180.71.3,304,0\n80.91.33.133,200,490\n"
+        );
+        assert_eq!(
+            logs_to_csv(&logs[1..], &["method", "url", "time"]),
+            "method,url,time\nGET,\"/search?q=a,b\",2015-05-17T08:05:24+00:00\n"
+        );
+        assert_eq!(csv_escape(r#"say "hi""#), r#""say ""hi""""#);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_nginx_log_stream_should_work() -> Result<()> {
+        let s = r#"93.180.71.3 - - [17/May/2015:08:05:32 +0000] "GET /downloads/product_1 HTTP/1.1" 304 0 "-" "Debian APT-HTTP/1.3 (0.8.16~exp12ubuntu10.21)"
+this is not a log line
+
+80.91.33.133 - - [17/May/2015:08:05:24 +0000] "GET /downloads/product_2 HTTP/1.1" 200 490 "-" "Debian APT-HTTP/1.3 (0.8.16~exp12ubuntu10.17)"
+"#;
+        let logs: Vec<NginxLog> = NginxLogStream::new(s.as_bytes())
+            .skip_errors(true)
+            .collect::<Result<_>>()?;
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[1].url, "/downloads/product_2");
+
+        let ret: Vec<Result<NginxLog>> = NginxLogStream::new(s.as_bytes()).collect();
+        assert_eq!(ret.len(), 3);
+        assert!(ret[0].is_ok());
+        let err = ret[1].as_ref().unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Failed to parse log at line 2, column 1:"));
+        assert!(ret[2].is_ok());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,266 @@
+use winnow::{
+    combinator::{alt, delimited, eof, preceded, repeat, rest, separated_pair, terminated},
+    error::{ContextError, ErrMode, ErrorKind, ParserError},
+    stream::AsChar,
+    token::{any, none_of, take_till, take_until, take_while},
+    PResult, Parser,
+};
+
+/// A string delimited by `quote`.
+///
+/// With `escape` enabled, JSON-style backslash escapes (including `\uXXXX` surrogate pairs)
+/// are decoded and an escaped `quote` doesn't terminate the string. Without it, the content
+/// is taken verbatim up to the next `quote`. Either way the content may be empty.
+pub fn quoted<'a>(quote: char, escape: bool) -> impl Parser<&'a str, String, ContextError> {
+    move |input: &mut &'a str| {
+        if escape {
+            let content = repeat(
+                0..,
+                alt((
+                    |i: &mut &'a str| parse_escaped_char(i, quote),
+                    none_of([quote, '\\']),
+                )),
+            );
+            delimited(quote, content, quote).parse_next(input)
+        } else {
+            delimited(quote, take_until(0.., quote), quote)
+                .map(|s: &str| s.to_string())
+                .parse_next(input)
+        }
+    }
+}
+
+/// Convert a byte offset into `input` to a 1-based (line, column) pair, columns count chars.
+pub fn line_column(input: &str, offset: usize) -> (usize, usize) {
+    let before = &input[..offset.min(input.len())];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (line, before[line_start..].chars().count() + 1)
+}
+
+/// Return up to `radius` chars of `input` on either side of the byte `offset`.
+pub fn snippet(input: &str, offset: usize, radius: usize) -> &str {
+    let offset = offset.min(input.len());
+    let start = input[..offset]
+        .char_indices()
+        .rev()
+        .take(radius)
+        .last()
+        .map_or(offset, |(i, _)| i);
+    let end = input[offset..]
+        .char_indices()
+        .nth(radius)
+        .map_or(input.len(), |(i, _)| offset + i);
+    &input[start..end]
+}
+
+/// Split `line` into exactly `count` whitespace-separated columns. The last column takes
+/// the remainder of the line, internal whitespace included.
+pub fn parse_ws_columns(line: &str, count: usize) -> PResult<Vec<String>> {
+    let input = &mut line.trim();
+    if count == 0 {
+        eof.parse_next(input)?;
+        return Ok(vec![]);
+    }
+    let column = take_till(1.., char::is_whitespace).map(|c: &str| c.to_string());
+    let mut columns: Vec<String> = repeat(
+        count - 1,
+        terminated(column, take_while(1.., char::is_whitespace)),
+    )
+    .parse_next(input)?;
+    let last = rest.verify(|r: &str| !r.is_empty()).parse_next(input)?;
+    columns.push(last.to_string());
+    Ok(columns)
+}
+
+/// Which lines [`parse_kv_block_with`] skips instead of rejecting.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KvBlockOptions {
+    /// Skip lines starting with `#` or `;`.
+    pub skip_comments: bool,
+    /// Skip empty or whitespace-only lines.
+    pub skip_blank_lines: bool,
+}
+
+/// Parse `key<sep>value` lines into trimmed pairs, skipping comments and blank lines. The
+/// value is everything after the first `sep`, so it may contain `sep` itself.
+pub fn parse_kv_block(s: &str, sep: char) -> PResult<Vec<(String, String)>> {
+    let options = KvBlockOptions {
+        skip_comments: true,
+        skip_blank_lines: true,
+    };
+    parse_kv_block_with(s, sep, &options)
+}
+
+/// Like [`parse_kv_block`], only skipping the lines selected by `options`.
+pub fn parse_kv_block_with(
+    s: &str,
+    sep: char,
+    options: &KvBlockOptions,
+) -> PResult<Vec<(String, String)>> {
+    let mut pairs = vec![];
+    for line in s.lines().map(str::trim) {
+        if (options.skip_blank_lines && line.is_empty())
+            || (options.skip_comments && line.starts_with(['#', ';']))
+        {
+            continue;
+        }
+        let key = take_till(1.., sep)
+            .map(str::trim)
+            .verify(|k: &str| !k.is_empty());
+        let (k, v) = separated_pair(key, sep, rest.map(str::trim)).parse_next(&mut &*line)?;
+        pairs.push((k.to_string(), v.to_string()));
+    }
+    Ok(pairs)
+}
+
+fn parse_escaped_char(input: &mut &str, quote: char) -> PResult<char> {
+    preceded(
+        '\\',
+        alt((
+            any.verify(move |c: &char| *c == quote || matches!(c, '"' | '\\' | '/')),
+            'b'.value('\u{8}'),
+            'f'.value('\u{c}'),
+            'n'.value('\n'),
+            'r'.value('\r'),
+            't'.value('\t'),
+            preceded('u', parse_unicode_escape),
+        )),
+    )
+    .parse_next(input)
+}
+
+// a high surrogate must be followed by an escaped low surrogate to form a single char
+fn parse_unicode_escape(input: &mut &str) -> PResult<char> {
+    let high = parse_hex4(input)?;
+    let code = if (0xD800..0xDC00).contains(&high) {
+        let low = preceded("\\u", parse_hex4)
+            .verify(|low| (0xDC00..0xE000).contains(low))
+            .parse_next(input)?;
+        0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+    } else {
+        high
+    };
+    char::from_u32(code).ok_or_else(|| ErrMode::from_error_kind(input, ErrorKind::Verify))
+}
+
+fn parse_hex4(input: &mut &str) -> PResult<u32> {
+    take_while(4, AsChar::is_hex_digit)
+        .try_map(|hex| u32::from_str_radix(hex, 16))
+        .parse_next(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quoted_with_escape_should_work() {
+        let mut s = r#""say \"hi\"\n\u00e9\ud83d\ude00" rest"#;
+        let ret = quoted('"', true).parse_next(&mut s).unwrap();
+        assert_eq!(ret, "say \"hi\"\né😀");
+        assert_eq!(s, " rest");
+
+        let mut s = r"'it\'s'";
+        let ret = quoted('\'', true).parse_next(&mut s).unwrap();
+        assert_eq!(ret, "it's");
+
+        let mut s = r#""bad \x escape""#;
+        assert!(quoted('"', true).parse_next(&mut s).is_err());
+
+        let mut s = r#""lone \udc00 surrogate""#;
+        assert!(quoted('"', true).parse_next(&mut s).is_err());
+    }
+
+    #[test]
+    fn line_column_should_work() {
+        let s = "{\n  \"a\": @\n}";
+        assert_eq!(line_column(s, 0), (1, 1));
+        assert_eq!(line_column(s, 2), (2, 1));
+        assert_eq!(line_column(s, 9), (2, 8));
+        assert_eq!(line_column(s, 100), (3, 2));
+    }
+
+    #[test]
+    fn snippet_should_work() {
+        let s = r#"{"name": @, "age": 43}"#;
+        assert_eq!(snippet(s, 9, 3), r#"": @, "#);
+        assert_eq!(snippet(s, 1, 3), r#"{"na"#);
+        assert_eq!(snippet(s, s.len(), 3), "43}");
+        assert_eq!(snippet("añb", 3, 1), "ñb");
+        assert_eq!(snippet(s, 9, 0), "");
+    }
+
+    #[test]
+    fn quoted_without_escape_should_work() {
+        let mut s = r#""Debian APT-HTTP/1.3 (0.8.16~exp12ubuntu10.21)" "-""#;
+        let ret = quoted('"', false).parse_next(&mut s).unwrap();
+        assert_eq!(ret, "Debian APT-HTTP/1.3 (0.8.16~exp12ubuntu10.21)");
+        assert_eq!(s, r#" "-""#);
+
+        let mut s = r#""C:\temp\""#;
+        let ret = quoted('"', false).parse_next(&mut s).unwrap();
+        assert_eq!(ret, r"C:\temp\");
+
+        let mut s = r#""""#;
+        assert_eq!(quoted('"', false).parse_next(&mut s).unwrap(), "");
+    }
+
+    #[test]
+    fn parse_ws_columns_should_work() {
+        let ret = parse_ws_columns("1234  (my app)\tS 1 1234 1234", 3).unwrap();
+        assert_eq!(ret, vec!["1234", "(my", "app)\tS 1 1234 1234"]);
+
+        let ret = parse_ws_columns(" 42 \n", 1).unwrap();
+        assert_eq!(ret, vec!["42"]);
+    }
+
+    #[test]
+    fn parse_ws_columns_with_too_few_columns_should_fail() {
+        assert!(parse_ws_columns("1234 (app)", 3).is_err());
+        assert!(parse_ws_columns("1234 (app)  ", 3).is_err());
+        assert!(parse_ws_columns("", 1).is_err());
+    }
+
+    #[test]
+    fn parse_kv_block_should_work() {
+        let s = "time_namelookup:  0.004\n# connection\ntime_connect: 0.012\n\nurl: http://x:80/\n";
+        let ret = parse_kv_block(s, ':').unwrap();
+        assert_eq!(
+            ret,
+            vec![
+                ("time_namelookup".to_string(), "0.004".to_string()),
+                ("time_connect".to_string(), "0.012".to_string()),
+                ("url".to_string(), "http://x:80/".to_string()),
+            ]
+        );
+
+        let s = "; settings\nHOME = /root\nQUERY=a=b\n";
+        let ret = parse_kv_block(s, '=').unwrap();
+        assert_eq!(
+            ret,
+            vec![
+                ("HOME".to_string(), "/root".to_string()),
+                ("QUERY".to_string(), "a=b".to_string()),
+            ]
+        );
+        assert!(parse_kv_block("no separator", '=').is_err());
+        assert!(parse_kv_block(" = value", '=').is_err());
+    }
+
+    #[test]
+    fn parse_kv_block_with_options_should_work() {
+        let s = "# comment\na=1\n";
+        assert!(parse_kv_block_with(s, '=', &KvBlockOptions::default()).is_err());
+
+        let options = KvBlockOptions {
+            skip_comments: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            parse_kv_block_with(s, '=', &options).unwrap(),
+            vec![("a".to_string(), "1".to_string())]
+        );
+        assert!(parse_kv_block_with("a=1\n\nb=2", '=', &options).is_err());
+    }
+}
@@ -0,0 +1,69 @@
+use std::net::IpAddr;
+
+use winnow::{
+    ascii::{digit1, float, space1},
+    combinator::{eof, opt, preceded, terminated},
+    PResult, Parser,
+};
+
+use crate::nginx_log::parse_ip;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PingReply {
+    pub bytes: u32,
+    pub from: IpAddr,
+    pub icmp_seq: u32,
+    pub ttl: u8,
+    pub time_ms: f64,
+}
+
+// need to parse:
+// 64 bytes from 1.1.1.1: icmp_seq=1 ttl=57 time=12.3 ms
+pub fn parse_ping_line(s: &str) -> PResult<PingReply> {
+    let input = &mut s.trim_end();
+    let bytes = terminated(digit1.parse_to(), " bytes from ").parse_next(input)?;
+    let from = terminated(parse_ip, ':').parse_next(input)?;
+    let icmp_seq = preceded((space1, "icmp_seq="), digit1.parse_to()).parse_next(input)?;
+    let ttl = preceded((space1, "ttl="), digit1.parse_to()).parse_next(input)?;
+    let time_ms = preceded((space1, "time="), float).parse_next(input)?;
+    (opt(" ms"), eof).parse_next(input)?;
+    Ok(PingReply {
+        bytes,
+        from,
+        icmp_seq,
+        ttl,
+        time_ms,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    #[test]
+    fn parse_ping_line_should_work() {
+        let ret = parse_ping_line("64 bytes from 1.1.1.1: icmp_seq=1 ttl=57 time=12.3 ms").unwrap();
+        assert_eq!(
+            ret,
+            PingReply {
+                bytes: 64,
+                from: IpAddr::V4(Ipv4Addr::new(1, 1, 1, 1)),
+                icmp_seq: 1,
+                ttl: 57,
+                time_ms: 12.3,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_ping_summary_lines_should_fail() {
+        assert!(parse_ping_line("PING 1.1.1.1 (1.1.1.1): 56 data bytes").is_err());
+        assert!(parse_ping_line("--- 1.1.1.1 ping statistics ---").is_err());
+        assert!(
+            parse_ping_line("4 packets transmitted, 4 packets received, 0.0% packet loss").is_err()
+        );
+        assert!(parse_ping_line("round-trip min/avg/max/stddev = 11.2/12.0/12.9/0.6 ms").is_err());
+    }
+}
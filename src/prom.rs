@@ -0,0 +1,124 @@
+use winnow::{
+    ascii::{dec_int, space0, space1},
+    combinator::{delimited, eof, opt, preceded, separated, separated_pair, terminated},
+    token::{one_of, take_till, take_while},
+    PResult, Parser,
+};
+
+use crate::parsers::quoted;
+
+/// A single sample from the Prometheus text exposition format.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sample {
+    pub name: String,
+    pub labels: Vec<(String, String)>,
+    pub value: f64,
+    pub timestamp: Option<i64>,
+}
+
+/// Parse every sample in an exposition document, skipping blank lines and `#` comments
+/// (including `# HELP` and `# TYPE`).
+pub fn parse_prometheus(s: &str) -> PResult<Vec<Sample>> {
+    s.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(parse_sample)
+        .collect()
+}
+
+// need to parse:
+// http_requests_total{method="post",code="200"} 1027 1395066363000
+pub fn parse_sample(s: &str) -> PResult<Sample> {
+    let input = &mut (&*s);
+    let name = parse_name(input)?;
+    let labels = opt(parse_labels).parse_next(input)?.unwrap_or_default();
+    space1(input)?;
+    let value = parse_value(input)?;
+    let timestamp = opt(preceded(space1, dec_int)).parse_next(input)?;
+    (space0, eof).parse_next(input)?;
+    Ok(Sample {
+        name: name.to_string(),
+        labels,
+        value,
+        timestamp,
+    })
+}
+
+fn parse_name<'a>(s: &mut &'a str) -> PResult<&'a str> {
+    (
+        one_of(|c: char| c.is_ascii_alphabetic() || c == '_' || c == ':'),
+        take_while(0.., |c: char| {
+            c.is_ascii_alphanumeric() || c == '_' || c == ':'
+        }),
+    )
+        .take()
+        .parse_next(s)
+}
+
+fn parse_labels(s: &mut &str) -> PResult<Vec<(String, String)>> {
+    let sep_comma = (space0, ',', space0);
+    let label = separated_pair(
+        parse_name.map(|n| n.to_string()),
+        (space0, '=', space0),
+        quoted('"', true),
+    );
+    let labels = terminated(separated(0.., label, sep_comma), opt((space0, ',')));
+    delimited(('{', space0), labels, (space0, '}')).parse_next(s)
+}
+
+// values may also be `NaN`, `+Inf` or `-Inf`, which `f64::from_str` understands
+fn parse_value(s: &mut &str) -> PResult<f64> {
+    take_till(1.., |c: char| c.is_ascii_whitespace())
+        .parse_to()
+        .parse_next(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_labeled_sample_should_work() {
+        let ret =
+            parse_sample(r#"http_requests_total{method="post",code="200"} 1027 1395066363000"#)
+                .unwrap();
+        assert_eq!(
+            ret,
+            Sample {
+                name: "http_requests_total".to_string(),
+                labels: vec![
+                    ("method".to_string(), "post".to_string()),
+                    ("code".to_string(), "200".to_string()),
+                ],
+                value: 1027.0,
+                timestamp: Some(1395066363000),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_unlabeled_sample_should_work() {
+        let ret = parse_sample("metric_without_timestamp_and_labels 12.47").unwrap();
+        assert_eq!(ret.name, "metric_without_timestamp_and_labels");
+        assert!(ret.labels.is_empty());
+        assert_eq!(ret.value, 12.47);
+        assert_eq!(ret.timestamp, None);
+
+        let ret = parse_sample(r#"go_gc_duration_seconds{quantile="1"} +Inf"#).unwrap();
+        assert_eq!(ret.value, f64::INFINITY);
+    }
+
+    #[test]
+    fn parse_prometheus_should_skip_comments() {
+        let s = r#"
+# HELP http_requests_total The total number of HTTP requests.
+# TYPE http_requests_total counter
+http_requests_total{method="post",code="200"} 1027 1395066363000
+http_requests_total{method="post",code="400"}    3 1395066363000
+"#;
+        let ret = parse_prometheus(s).unwrap();
+        assert_eq!(ret.len(), 2);
+        assert_eq!(ret[1].labels[1], ("code".to_string(), "400".to_string()));
+        assert_eq!(ret[1].value, 3.0);
+    }
+}
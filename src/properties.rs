@@ -0,0 +1,120 @@
+use indexmap::IndexMap;
+
+use winnow::{
+    ascii::space0,
+    combinator::{alt, dispatch, empty, eof, opt, preceded, repeat, terminated},
+    token::{any, none_of, one_of, take_while},
+    PResult, Parser,
+};
+
+/// Parse a Java `.properties` file, keeping entries in file order. A repeated key keeps its
+/// first position but takes the last value.
+// need to parse:
+// # comment
+// name = demo
+// path:/usr/local/\
+//      bin
+// greeting=caf\u00e9
+pub fn parse_properties(s: &str) -> PResult<IndexMap<String, String>> {
+    let mut props = IndexMap::new();
+    for line in logical_lines(s) {
+        let (key, value) = parse_entry(&mut line.as_str())?;
+        props.insert(key, value);
+    }
+    Ok(props)
+}
+
+// join continued lines and drop blank and comment lines, leading whitespace is insignificant
+fn logical_lines(s: &str) -> Vec<String> {
+    let mut lines = vec![];
+    let mut current: Option<String> = None;
+    for line in s.lines() {
+        let line = line.trim_start_matches([' ', '\t', '\x0c']);
+        if current.is_none() && (line.is_empty() || line.starts_with(['#', '!'])) {
+            continue;
+        }
+        // an odd number of trailing backslashes escapes the line break
+        let backslashes = line.len() - line.trim_end_matches('\\').len();
+        let (line, continued) = match backslashes % 2 {
+            1 => (&line[..line.len() - 1], true),
+            _ => (line, false),
+        };
+        let joined = current.get_or_insert_with(String::new);
+        joined.push_str(line);
+        if !continued {
+            lines.extend(current.take());
+        }
+    }
+    lines.extend(current);
+    lines
+}
+
+fn parse_entry(s: &mut &str) -> PResult<(String, String)> {
+    let key = repeat(
+        0..,
+        alt((parse_escape, none_of([' ', '\t', '\x0c', '=', ':', '\\']))),
+    )
+    .parse_next(s)?;
+    (space0, opt(one_of(['=', ':'])), space0).parse_next(s)?;
+    let value = terminated(repeat(0.., alt((parse_escape, none_of('\\')))), eof).parse_next(s)?;
+    Ok((key, value))
+}
+
+// unknown escapes stand for the escaped char itself, e.g. `\=` or `\ `
+fn parse_escape(s: &mut &str) -> PResult<char> {
+    preceded(
+        '\\',
+        dispatch! {any;
+            't' => empty.value('\t'),
+            'n' => empty.value('\n'),
+            'r' => empty.value('\r'),
+            'f' => empty.value('\x0c'),
+            'u' => take_while(4, |c: char| c.is_ascii_hexdigit())
+                .try_map(|hex| u32::from_str_radix(hex, 16))
+                .verify_map(char::from_u32),
+            c => empty.value(c),
+        },
+    )
+    .parse_next(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_properties_should_work() {
+        let s = "# app settings\n! legacy comment\nname = demo app\n\nport:8080\n  timeout   10\nempty=\n";
+        let ret = parse_properties(s).unwrap();
+        let entries: Vec<(&str, &str)> =
+            ret.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+        assert_eq!(
+            entries,
+            vec![
+                ("name", "demo app"),
+                ("port", "8080"),
+                ("timeout", "10"),
+                ("empty", ""),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_properties_with_continuation_should_work() {
+        let s = "fruits = apple, banana, \\\n         cherry, \\\n         date\npath=C:\\\\temp\\\\\nnext=1\n";
+        let ret = parse_properties(s).unwrap();
+        assert_eq!(ret["fruits"], "apple, banana, cherry, date");
+        // an even number of backslashes doesn't continue the line
+        assert_eq!(ret["path"], "C:\\temp\\");
+        assert_eq!(ret["next"], "1");
+    }
+
+    #[test]
+    fn parse_properties_with_escapes_should_work() {
+        let s = "greeting=caf\\u00e9\\tbar\nkey\\ with\\=sep = value\n";
+        let ret = parse_properties(s).unwrap();
+        assert_eq!(ret["greeting"], "café\tbar");
+        assert_eq!(ret["key with=sep"], "value");
+        assert!(parse_properties("bad=\\u00zz").is_err());
+    }
+}
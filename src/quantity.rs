@@ -0,0 +1,137 @@
+use winnow::{
+    ascii::{dec_int, digit0},
+    combinator::{alt, empty, eof, opt, preceded, terminated},
+    error::{ErrMode, ErrorKind, ParserError},
+    token::one_of,
+    PResult, Parser,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Scale {
+    // power of ten
+    Decimal(i32),
+    // power of two
+    Binary(u32),
+}
+
+/// Parse a Kubernetes resource quantity, e.g. `250m`, `1.5`, `500Mi` or `1e3`. Quantities
+/// with a sub-unit suffix (`m`, `u`, `n` or a negative exponent) are returned in milli-units,
+/// e.g. `250m` CPU is 250 millicores, all others in whole base units, e.g. `1Gi` of memory
+/// is `1_073_741_824` bytes. Anything finer is rounded up, so `1n` is 1 and `1.5` is 2.
+// need to parse:
+// 500Mi
+pub fn parse_quantity(s: &str) -> PResult<i64> {
+    let input = &mut s.trim();
+    let negative = opt(one_of(['+', '-']))
+        .map(|sign| sign == Some('-'))
+        .parse_next(input)?;
+    let (int, frac) = (
+        digit0,
+        opt(preceded('.', digit0)).map(Option::unwrap_or_default),
+    )
+        .verify(|(int, frac): &(&str, &str)| !int.is_empty() || !frac.is_empty())
+        .parse_next(input)?;
+    let scale = terminated(parse_scale, eof).parse_next(input)?;
+
+    let overflow = |input: &&str| ErrMode::from_error_kind(input, ErrorKind::Verify);
+    let mantissa = format!("{}{}", int, frac)
+        .parse::<i128>()
+        .map_err(|_| overflow(input))?;
+    let (exp10, exp2) = match scale {
+        Scale::Decimal(e) => (e, 0),
+        Scale::Binary(e) => (0, e),
+    };
+    // shift by 3 for milli-units, and back by the fraction we folded into the mantissa
+    let milli = if exp10 < 0 { 3 } else { 0 };
+    let exp10 = exp10 as i64 + milli - frac.len() as i64;
+    let mut v = 2i128
+        .checked_pow(exp2)
+        .and_then(|m| mantissa.checked_mul(m))
+        .ok_or_else(|| overflow(input))?;
+    if exp10 >= 0 {
+        v = u32::try_from(exp10)
+            .ok()
+            .and_then(|e| 10i128.checked_pow(e))
+            .and_then(|m| v.checked_mul(m))
+            .ok_or_else(|| overflow(input))?;
+    } else {
+        // divisors beyond i128 leave at most the round-up
+        v = match u32::try_from(-exp10)
+            .ok()
+            .and_then(|e| 10i128.checked_pow(e))
+        {
+            Some(d) => v / d + i128::from(v % d != 0),
+            None => i128::from(v > 0),
+        };
+    }
+    let v = if negative { -v } else { v };
+    i64::try_from(v).map_err(|_| overflow(input))
+}
+
+fn parse_scale(input: &mut &str) -> PResult<Scale> {
+    alt((
+        // `E` alone is the exa suffix, an exponent needs digits
+        preceded(one_of(['e', 'E']), dec_int).map(Scale::Decimal),
+        "Ki".value(Scale::Binary(10)),
+        "Mi".value(Scale::Binary(20)),
+        "Gi".value(Scale::Binary(30)),
+        "Ti".value(Scale::Binary(40)),
+        "Pi".value(Scale::Binary(50)),
+        "Ei".value(Scale::Binary(60)),
+        'n'.value(Scale::Decimal(-9)),
+        'u'.value(Scale::Decimal(-6)),
+        'm'.value(Scale::Decimal(-3)),
+        'k'.value(Scale::Decimal(3)),
+        'M'.value(Scale::Decimal(6)),
+        'G'.value(Scale::Decimal(9)),
+        'T'.value(Scale::Decimal(12)),
+        'P'.value(Scale::Decimal(15)),
+        'E'.value(Scale::Decimal(18)),
+        empty.value(Scale::Decimal(0)),
+    ))
+    .parse_next(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_quantity_should_work() {
+        assert_eq!(parse_quantity("1Gi").unwrap(), 1_073_741_824);
+        assert_eq!(parse_quantity("500Mi").unwrap(), 524_288_000);
+        assert_eq!(parse_quantity("10Pi").unwrap(), 11_258_999_068_426_240);
+        assert_eq!(parse_quantity("2k").unwrap(), 2000);
+        assert_eq!(parse_quantity("1G").unwrap(), 1_000_000_000);
+        assert_eq!(parse_quantity(".5Ki").unwrap(), 512);
+        assert_eq!(parse_quantity("1e3").unwrap(), 1000);
+        assert_eq!(parse_quantity("-2").unwrap(), -2);
+        assert_eq!(parse_quantity("1.5").unwrap(), 2);
+    }
+
+    #[test]
+    fn parse_sub_unit_quantity_should_return_milli_units() {
+        assert_eq!(parse_quantity("250m").unwrap(), 250);
+        assert_eq!(parse_quantity("1.5m").unwrap(), 2);
+        assert_eq!(parse_quantity("500u").unwrap(), 1);
+        assert_eq!(parse_quantity("1n").unwrap(), 1);
+        assert_eq!(parse_quantity("25e-2").unwrap(), 250);
+    }
+
+    #[test]
+    fn parse_quantity_with_invalid_input_should_fail() {
+        for s in [
+            "",
+            "m",
+            "1Gb",
+            "1.2.3",
+            "Gi",
+            "1ki",
+            "9Ei",
+            "1e100",
+            "170141183460469231731687303715884105727n",
+        ] {
+            assert!(parse_quantity(s).is_err(), "{s:?} should fail");
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use winnow::{
+    ascii::{line_ending, space0},
+    combinator::{alt, delimited, eof, opt, preceded, repeat, separated, terminated},
+    stream::AsChar,
+    token::{take_till, take_while},
+    PResult, Parser,
+};
+
+use crate::parsers::quoted;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegValue {
+    Sz(String),
+    Dword(u32),
+    /// Raw bytes of `hex:` and typed `hex(n):` values.
+    Hex(Vec<u8>),
+}
+
+/// A registry key path and its named values, the default value is named `""`.
+pub type RegKey = (String, Vec<(String, RegValue)>);
+
+// need to parse:
+// Windows Registry Editor Version 5.00
+//
+// [HKEY_CURRENT_USER\Software\Demo]
+// "Name"="demo"
+// "Count"=dword:0000000a
+// "Data"=hex:01,02,\
+//   03
+pub fn parse_reg(s: &str) -> PResult<Vec<RegKey>> {
+    let input = &mut s.trim_start_matches('\u{feff}');
+    skip_blank(input)?;
+    let header = alt(("Windows Registry Editor Version 5.00", "REGEDIT4"));
+    opt(terminated(header, parse_eol)).parse_next(input)?;
+    let keys = repeat(0.., preceded(skip_blank, parse_key)).parse_next(input)?;
+    (skip_blank, space0, eof).parse_next(input)?;
+    Ok(keys)
+}
+
+fn parse_key(s: &mut &str) -> PResult<RegKey> {
+    let path = delimited('[', take_till(1.., [']', '\r', '\n']), ']').parse_next(s)?;
+    parse_eol(s)?;
+    let values = repeat(0.., preceded(skip_blank, parse_named_value)).parse_next(s)?;
+    Ok((path.to_string(), values))
+}
+
+fn parse_named_value(s: &mut &str) -> PResult<(String, RegValue)> {
+    let name = alt((quoted('"', true), '@'.value(String::new()))).parse_next(s)?;
+    let value = preceded('=', parse_value).parse_next(s)?;
+    parse_eol(s)?;
+    Ok((name, value))
+}
+
+fn parse_value(s: &mut &str) -> PResult<RegValue> {
+    alt((
+        quoted('"', true).map(RegValue::Sz),
+        preceded("dword:", take_while(8, AsChar::is_hex_digit))
+            .try_map(|hex| u32::from_str_radix(hex, 16))
+            .map(RegValue::Dword),
+        preceded(
+            (
+                "hex",
+                opt(delimited('(', take_while(1.., AsChar::is_hex_digit), ')')),
+                ':',
+            ),
+            parse_hex_bytes,
+        )
+        .map(RegValue::Hex),
+    ))
+    .parse_next(s)
+}
+
+// long byte lists are wrapped with a trailing `\` and continue indented on the next line
+fn parse_hex_bytes(s: &mut &str) -> PResult<Vec<u8>> {
+    let byte = take_while(2, AsChar::is_hex_digit).try_map(|hex| u8::from_str_radix(hex, 16));
+    let sep = (',', opt(('\\', line_ending, space0)));
+    separated(0.., byte, sep).parse_next(s)
+}
+
+fn parse_eol(s: &mut &str) -> PResult<()> {
+    (space0, alt((line_ending, eof))).void().parse_next(s)
+}
+
+// blank lines and `;` comments
+fn skip_blank(s: &mut &str) -> PResult<()> {
+    let comment = (';', take_till(0.., ['\r', '\n']));
+    repeat(0.., (space0, opt(comment), line_ending))
+        .map(|()| ())
+        .parse_next(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_reg_should_work() {
+        let s = "Windows Registry Editor Version 5.00\r\n\r\n[HKEY_CURRENT_USER\\Software\\Demo]\r\n\"Name\"=\"C:\\\\Program Files\\\\Demo\"\r\n\"Count\"=dword:0000000a\r\n; binary data\r\n\"Data\"=hex:01,02,\\\r\n  ff\r\n@=\"default\"\r\n\r\n[HKEY_CURRENT_USER\\Software\\Demo\\Empty]\r\n";
+        let ret = parse_reg(s).unwrap();
+        assert_eq!(
+            ret,
+            vec![
+                (
+                    "HKEY_CURRENT_USER\\Software\\Demo".to_string(),
+                    vec![
+                        (
+                            "Name".to_string(),
+                            RegValue::Sz("C:\\Program Files\\Demo".to_string())
+                        ),
+                        ("Count".to_string(), RegValue::Dword(10)),
+                        ("Data".to_string(), RegValue::Hex(vec![1, 2, 0xff])),
+                        ("".to_string(), RegValue::Sz("default".to_string())),
+                    ]
+                ),
+                (
+                    "HKEY_CURRENT_USER\\Software\\Demo\\Empty".to_string(),
+                    vec![]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_reg_with_string_and_dword_should_work() {
+        let s =
+            "[HKEY_LOCAL_MACHINE\\SOFTWARE\\App]\n\"Version\"=\"1.0\"\n\"Enabled\"=dword:00000001";
+        let ret = parse_reg(s).unwrap();
+        assert_eq!(ret.len(), 1);
+        assert_eq!(
+            ret[0].1,
+            vec![
+                ("Version".to_string(), RegValue::Sz("1.0".to_string())),
+                ("Enabled".to_string(), RegValue::Dword(1)),
+            ]
+        );
+        assert!(parse_reg("[HKEY_LOCAL_MACHINE\\SOFTWARE\\App]\n\"Bad\"=dword:1\n").is_err());
+        assert!(parse_reg("\"Orphan\"=\"value\"\n").is_err());
+    }
+}
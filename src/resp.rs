@@ -0,0 +1,130 @@
+use winnow::{
+    ascii::dec_int,
+    combinator::{dispatch, eof, fail, repeat, terminated},
+    token::{any, take, take_till},
+    PResult, Parser,
+};
+
+/// A Redis serialization protocol (RESP2) value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resp {
+    Simple(String),
+    Error(String),
+    Integer(i64),
+    /// `None` for the null bulk string `$-1`.
+    Bulk(Option<String>),
+    /// `None` for the null array `*-1`.
+    Array(Option<Vec<Resp>>),
+}
+
+// need to parse:
+// *2\r\n$3\r\nfoo\r\n:42\r\n
+pub fn parse_resp(input: &[u8]) -> PResult<Resp> {
+    let input = &mut (&*input);
+    terminated(parse_frame, eof).parse_next(input)
+}
+
+fn parse_frame(s: &mut &[u8]) -> PResult<Resp> {
+    dispatch! {any;
+        b'+' => parse_line.map(Resp::Simple),
+        b'-' => parse_line.map(Resp::Error),
+        b':' => parse_int.map(Resp::Integer),
+        b'$' => parse_bulk.map(Resp::Bulk),
+        b'*' => parse_array.map(Resp::Array),
+        _ => fail,
+    }
+    .parse_next(s)
+}
+
+fn parse_line(s: &mut &[u8]) -> PResult<String> {
+    terminated(take_till(0.., ['\r', '\n']), "\r\n")
+        .try_map(|line: &[u8]| String::from_utf8(line.to_vec()))
+        .parse_next(s)
+}
+
+fn parse_int(s: &mut &[u8]) -> PResult<i64> {
+    terminated(dec_int, "\r\n").parse_next(s)
+}
+
+// a negative length stands for null
+fn parse_len(s: &mut &[u8]) -> PResult<Option<usize>> {
+    parse_int
+        .verify(|len| *len >= -1)
+        .map(|len| usize::try_from(len).ok())
+        .parse_next(s)
+}
+
+fn parse_bulk(s: &mut &[u8]) -> PResult<Option<String>> {
+    let Some(len) = parse_len(s)? else {
+        return Ok(None);
+    };
+    terminated(take(len), "\r\n")
+        .try_map(|data: &[u8]| String::from_utf8(data.to_vec()))
+        .map(Some)
+        .parse_next(s)
+}
+
+fn parse_array(s: &mut &[u8]) -> PResult<Option<Vec<Resp>>> {
+    let Some(len) = parse_len(s)? else {
+        return Ok(None);
+    };
+    repeat(len, parse_frame).map(Some).parse_next(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_simple_string_should_work() {
+        assert_eq!(
+            parse_resp(b"+OK\r\n").unwrap(),
+            Resp::Simple("OK".to_string())
+        );
+        assert_eq!(
+            parse_resp(b"-ERR unknown command\r\n").unwrap(),
+            Resp::Error("ERR unknown command".to_string())
+        );
+        assert_eq!(parse_resp(b":-42\r\n").unwrap(), Resp::Integer(-42));
+    }
+
+    #[test]
+    fn parse_bulk_string_should_work() {
+        assert_eq!(
+            parse_resp(b"$3\r\nfoo\r\n").unwrap(),
+            Resp::Bulk(Some("foo".to_string()))
+        );
+        // bulk strings are binary safe, so CRLF inside the payload is fine
+        assert_eq!(
+            parse_resp(b"$4\r\na\r\nb\r\n").unwrap(),
+            Resp::Bulk(Some("a\r\nb".to_string()))
+        );
+        assert!(parse_resp(b"$4\r\nfoo\r\n").is_err());
+    }
+
+    #[test]
+    fn parse_null_bulk_should_work() {
+        assert_eq!(parse_resp(b"$-1\r\n").unwrap(), Resp::Bulk(None));
+        assert_eq!(parse_resp(b"*-1\r\n").unwrap(), Resp::Array(None));
+        assert!(parse_resp(b"$-2\r\n").is_err());
+    }
+
+    #[test]
+    fn parse_array_should_work() {
+        assert_eq!(
+            parse_resp(b"*2\r\n$3\r\nfoo\r\n:42\r\n").unwrap(),
+            Resp::Array(Some(vec![
+                Resp::Bulk(Some("foo".to_string())),
+                Resp::Integer(42)
+            ]))
+        );
+        assert_eq!(
+            parse_resp(b"*2\r\n*1\r\n+a\r\n*0\r\n").unwrap(),
+            Resp::Array(Some(vec![
+                Resp::Array(Some(vec![Resp::Simple("a".to_string())])),
+                Resp::Array(Some(vec![])),
+            ]))
+        );
+        assert!(parse_resp(b"*2\r\n+a\r\n").is_err());
+    }
+}
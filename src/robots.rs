@@ -0,0 +1,111 @@
+use winnow::{
+    ascii::space0,
+    combinator::{rest, separated_pair},
+    token::take_till,
+    PResult, Parser,
+};
+
+/// The agents sharing a set of rules.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Group {
+    pub agents: Vec<String>,
+    pub rules: Vec<Rule>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Rule {
+    Allow(String),
+    Disallow(String),
+}
+
+// need to parse:
+// User-agent: *
+// Disallow: /private/
+// Allow: /private/public.html
+pub fn parse_robots(s: &str) -> PResult<Vec<Group>> {
+    let mut groups: Vec<Group> = Vec::new();
+    let lines = s
+        .lines()
+        .map(|line| line.split_once('#').map_or(line, |(line, _)| line).trim())
+        .filter(|line| !line.is_empty());
+    for line in lines {
+        let (field, value) = parse_line(line)?;
+        let rule = match field.to_ascii_lowercase().as_str() {
+            "user-agent" => {
+                // consecutive agent lines share the rules that follow them
+                match groups.last_mut() {
+                    Some(group) if group.rules.is_empty() => group.agents.push(value.to_string()),
+                    _ => groups.push(Group {
+                        agents: vec![value.to_string()],
+                        rules: vec![],
+                    }),
+                }
+                continue;
+            }
+            "allow" => Rule::Allow(value.to_string()),
+            "disallow" => Rule::Disallow(value.to_string()),
+            // e.g. `Sitemap` or `Crawl-delay`
+            _ => continue,
+        };
+        // rules before the first `User-agent` don't apply to anyone
+        if let Some(group) = groups.last_mut() {
+            group.rules.push(rule);
+        }
+    }
+    Ok(groups)
+}
+
+fn parse_line(s: &str) -> PResult<(&str, &str)> {
+    let input = &mut (&*s);
+    separated_pair(
+        take_till(1.., ':').map(str::trim),
+        (':', space0),
+        rest.map(str::trim),
+    )
+    .parse_next(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_robots_should_work() {
+        let s = "# robots for example.com\nUser-agent: *\nDisallow: /private/\n\nallow: /private/public.html # still fine\nSitemap: https://example.com/sitemap.xml\n";
+        let ret = parse_robots(s).unwrap();
+        assert_eq!(
+            ret,
+            vec![Group {
+                agents: vec!["*".to_string()],
+                rules: vec![
+                    Rule::Disallow("/private/".to_string()),
+                    Rule::Allow("/private/public.html".to_string()),
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_robots_with_shared_agents_should_work() {
+        let s = "User-agent: Googlebot\nUser-agent: Bingbot\nDisallow: /tmp\nDisallow:\n\nUser-agent: *\nDisallow: /\n";
+        let ret = parse_robots(s).unwrap();
+        assert_eq!(
+            ret,
+            vec![
+                Group {
+                    agents: vec!["Googlebot".to_string(), "Bingbot".to_string()],
+                    rules: vec![
+                        Rule::Disallow("/tmp".to_string()),
+                        Rule::Disallow("".to_string()),
+                    ],
+                },
+                Group {
+                    agents: vec!["*".to_string()],
+                    rules: vec![Rule::Disallow("/".to_string())],
+                },
+            ]
+        );
+
+        assert!(parse_robots("User-agent *").is_err());
+    }
+}
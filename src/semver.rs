@@ -0,0 +1,368 @@
+use std::cmp::Ordering;
+
+use winnow::{
+    ascii::{digit1, space0},
+    combinator::{alt, delimited, eof, opt, preceded, separated, terminated},
+    token::take_while,
+    PResult, Parser,
+};
+
+/// A semantic version, e.g. `1.2.3-rc.1+build.5`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub pre: Option<String>,
+    pub build: Option<String>,
+}
+
+/// The output of `git describe --tags`, e.g. `v1.2.3-5-gabc1234-dirty`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitDescribe {
+    pub version: Version,
+    pub commits_since: u32,
+    pub short_hash: String,
+    pub dirty: bool,
+}
+
+/// A cargo-style version requirement, e.g. `>=1.0, <2.0`. Every comparator must match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionReq {
+    pub comparators: Vec<Comparator>,
+}
+
+/// A single constraint of a [`VersionReq`], minor and patch may be omitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comparator {
+    pub op: Op,
+    pub major: u64,
+    pub minor: Option<u64>,
+    pub patch: Option<u64>,
+    pub pre: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Exact,
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+    Tilde,
+    Caret,
+}
+
+/// Parse a full `major.minor.patch` version with optional pre-release and build metadata.
+pub fn parse_version(s: &str) -> PResult<Version> {
+    let input = &mut (&*s);
+    terminated(version, eof).parse_next(input)
+}
+
+// need to parse:
+// ^1.2, ~1.2.3, >=1.0, <2.0, *
+pub fn parse_version_req(s: &str) -> PResult<VersionReq> {
+    let input = &mut (&*s);
+    let comparators = alt((
+        '*'.value(vec![]),
+        separated(1.., parse_comparator, (space0, ',', space0)),
+    ));
+    delimited(space0, comparators, (space0, eof))
+        .map(|comparators| VersionReq { comparators })
+        .parse_next(input)
+}
+
+/// Parse `git describe` output. When HEAD is exactly on a tag, `commits_since` is 0 and
+/// `short_hash` is empty.
+pub fn parse_git_describe(s: &str) -> PResult<GitDescribe> {
+    let (s, dirty) = match s.strip_suffix("-dirty") {
+        Some(s) => (s, true),
+        None => (s, false),
+    };
+    // a pre-release may itself contain `-`, so split the `-N-gHASH` suffix off from the end
+    let described = match s.rsplitn(3, '-').collect::<Vec<_>>()[..] {
+        [hash, count, tag] => parse_describe_suffix(count, hash)
+            .ok()
+            .map(|(count, hash)| (tag, count, hash)),
+        _ => None,
+    };
+    let (tag, commits_since, short_hash) = described.unwrap_or((s, 0, ""));
+    let input = &mut (&*tag);
+    let version = terminated(preceded(opt('v'), version), eof).parse_next(input)?;
+    Ok(GitDescribe {
+        version,
+        commits_since,
+        short_hash: short_hash.to_string(),
+        dirty,
+    })
+}
+
+fn parse_describe_suffix<'a>(count: &str, hash: &'a str) -> PResult<(u32, &'a str)> {
+    let count = terminated(digit1.parse_to::<u32>(), eof).parse_next(&mut &*count)?;
+    let hash = terminated(
+        preceded('g', take_while(4.., |c: char| c.is_ascii_hexdigit())),
+        eof,
+    )
+    .parse_next(&mut &*hash)?;
+    Ok((count, hash))
+}
+
+pub(crate) fn version(s: &mut &str) -> PResult<Version> {
+    let (major, _, minor, _, patch) =
+        (parse_number, '.', parse_number, '.', parse_number).parse_next(s)?;
+    let pre = opt(preceded('-', parse_identifiers)).parse_next(s)?;
+    let build = opt(preceded('+', parse_identifiers)).parse_next(s)?;
+    Ok(Version {
+        major,
+        minor,
+        patch,
+        pre,
+        build,
+    })
+}
+
+fn parse_comparator(s: &mut &str) -> PResult<Comparator> {
+    let op = opt(alt((
+        ">=".value(Op::GreaterEq),
+        ">".value(Op::Greater),
+        "<=".value(Op::LessEq),
+        "<".value(Op::Less),
+        "=".value(Op::Exact),
+        "~".value(Op::Tilde),
+        "^".value(Op::Caret),
+    )))
+    .parse_next(s)?;
+    space0(s)?;
+    let major = parse_number(s)?;
+    let minor = opt(preceded('.', parse_number)).parse_next(s)?;
+    let patch = match minor {
+        Some(_) => opt(preceded('.', parse_number)).parse_next(s)?,
+        None => None,
+    };
+    let pre = match patch {
+        Some(_) => opt(preceded('-', parse_identifiers)).parse_next(s)?,
+        None => None,
+    };
+    Ok(Comparator {
+        // a bare version behaves like a caret requirement, as in cargo
+        op: op.unwrap_or(Op::Caret),
+        major,
+        minor,
+        patch,
+        pre,
+    })
+}
+
+// numeric parts can't have leading zeros
+fn parse_number(s: &mut &str) -> PResult<u64> {
+    digit1
+        .verify(|d: &str| d == "0" || !d.starts_with('0'))
+        .parse_to()
+        .parse_next(s)
+}
+
+fn parse_identifiers(s: &mut &str) -> PResult<String> {
+    let identifier = take_while(1.., |c: char| c.is_ascii_alphanumeric() || c == '-');
+    separated(1.., identifier, '.')
+        .map(|()| ())
+        .take()
+        .map(|ids: &str| ids.to_string())
+        .parse_next(s)
+}
+
+impl Version {
+    pub fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Version {
+            major,
+            minor,
+            patch,
+            pre: None,
+            build: None,
+        }
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.pre, &other.pre) {
+                (None, None) => Ordering::Equal,
+                // a pre-release has lower precedence than the release itself
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => cmp_pre(a, b),
+            })
+            .then_with(|| self.build.cmp(&other.build))
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// numeric identifiers compare numerically and sort before alphanumeric ones
+fn cmp_pre(a: &str, b: &str) -> Ordering {
+    let key = |id: &str| match id.parse::<u64>() {
+        Ok(n) => (0, n, String::new()),
+        Err(_) => (1, 0, id.to_string()),
+    };
+    a.split('.').map(key).cmp(b.split('.').map(key))
+}
+
+impl VersionReq {
+    pub fn matches(&self, v: &Version) -> bool {
+        self.comparators.iter().all(|c| c.matches(v))
+            // pre-releases only match when explicitly asked for on the same version
+            && (v.pre.is_none()
+                || self.comparators.iter().any(|c| {
+                    c.pre.is_some()
+                        && (c.major, c.minor, c.patch)
+                            == (v.major, Some(v.minor), Some(v.patch))
+                }))
+    }
+}
+
+impl Comparator {
+    pub fn matches(&self, v: &Version) -> bool {
+        let lower = Version {
+            pre: self.pre.clone(),
+            ..Version::new(self.major, self.minor.unwrap_or(0), self.patch.unwrap_or(0))
+        };
+        let same_major = v.major == self.major;
+        let same_minor = same_major && self.minor.is_none_or(|minor| v.minor == minor);
+        let exact = same_minor
+            && self
+                .patch
+                .is_none_or(|patch| v.patch == patch && v.pre == self.pre);
+
+        match self.op {
+            Op::Exact => exact,
+            Op::Greater => match self.minor {
+                Some(minor) if self.patch.is_none() => {
+                    v.major > self.major || (same_major && v.minor > minor)
+                }
+                None => v.major > self.major,
+                Some(_) => *v > lower && !exact,
+            },
+            Op::GreaterEq => *v >= lower,
+            Op::Less => *v < lower,
+            Op::LessEq => *v < lower || exact,
+            Op::Tilde => *v >= lower && same_minor,
+            Op::Caret => {
+                *v >= lower
+                    && match (self.major, self.minor, self.patch) {
+                        (0, Some(0), Some(patch)) => same_minor && v.patch == patch,
+                        (0, Some(_), _) => same_minor,
+                        _ => same_major,
+                    }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(s: &str) -> Version {
+        parse_version(s).unwrap()
+    }
+
+    #[test]
+    fn parse_version_should_work() {
+        assert_eq!(v("1.2.3"), Version::new(1, 2, 3));
+        let ret = v("1.0.0-rc.1+build.5");
+        assert_eq!(ret.pre.as_deref(), Some("rc.1"));
+        assert_eq!(ret.build.as_deref(), Some("build.5"));
+
+        assert!(parse_version("1.2").is_err());
+        assert!(parse_version("01.2.3").is_err());
+        assert!(v("1.0.0-alpha") < v("1.0.0-alpha.1"));
+        assert!(v("1.0.0-alpha.2") < v("1.0.0-alpha.10"));
+        assert!(v("1.0.0-rc.1") < v("1.0.0"));
+    }
+
+    #[test]
+    fn parse_git_describe_should_work() {
+        let ret = parse_git_describe("v1.2.3-5-gabc1234").unwrap();
+        assert_eq!(
+            ret,
+            GitDescribe {
+                version: Version::new(1, 2, 3),
+                commits_since: 5,
+                short_hash: "abc1234".to_string(),
+                dirty: false,
+            }
+        );
+
+        let ret = parse_git_describe("v2.0.0-rc.1-12-g0f3e9a2b-dirty").unwrap();
+        assert_eq!(ret.version, v("2.0.0-rc.1"));
+        assert_eq!(ret.commits_since, 12);
+        assert_eq!(ret.short_hash, "0f3e9a2b");
+        assert!(ret.dirty);
+    }
+
+    #[test]
+    fn parse_git_describe_on_tag_should_work() {
+        let ret = parse_git_describe("v1.2.3").unwrap();
+        assert_eq!(ret.version, Version::new(1, 2, 3));
+        assert_eq!(ret.commits_since, 0);
+        assert_eq!(ret.short_hash, "");
+        assert!(!ret.dirty);
+
+        assert!(parse_git_describe("1.2.3-dirty").unwrap().dirty);
+        assert!(parse_git_describe("release-5-gabc1234").is_err());
+    }
+
+    #[test]
+    fn caret_req_should_work() {
+        let req = parse_version_req("^1.2").unwrap();
+        assert!(req.matches(&v("1.2.0")));
+        assert!(req.matches(&v("1.9.3")));
+        assert!(!req.matches(&v("1.1.9")));
+        assert!(!req.matches(&v("2.0.0")));
+
+        let req = parse_version_req("^0.2.3").unwrap();
+        assert!(req.matches(&v("0.2.9")));
+        assert!(!req.matches(&v("0.3.0")));
+
+        // a bare version is a caret requirement
+        assert_eq!(parse_version_req("1.2"), parse_version_req("^1.2"));
+    }
+
+    #[test]
+    fn tilde_req_should_work() {
+        let req = parse_version_req("~1.2.3").unwrap();
+        assert!(req.matches(&v("1.2.3")));
+        assert!(req.matches(&v("1.2.9")));
+        assert!(!req.matches(&v("1.3.0")));
+        assert!(!req.matches(&v("1.2.2")));
+    }
+
+    #[test]
+    fn combined_req_should_work() {
+        let req = parse_version_req(">=1.0, <2.0").unwrap();
+        assert_eq!(req.comparators.len(), 2);
+        assert!(req.matches(&v("1.0.0")));
+        assert!(req.matches(&v("1.99.0")));
+        assert!(!req.matches(&v("2.0.0")));
+        assert!(!req.matches(&v("0.9.9")));
+        assert!(!req.matches(&v("1.5.0-beta")));
+
+        let req = parse_version_req(">=1.5.0-beta, <2").unwrap();
+        assert!(req.matches(&v("1.5.0-beta.2")));
+
+        assert!(parse_version_req(">=1.0,").is_err());
+    }
+
+    #[test]
+    fn wildcard_req_should_work() {
+        let req = parse_version_req("*").unwrap();
+        assert!(req.matches(&v("0.0.1")));
+        assert!(req.matches(&v("42.1.0")));
+        assert!(!req.matches(&v("1.0.0-alpha")));
+    }
+}
@@ -0,0 +1,117 @@
+use winnow::{
+    ascii::multispace1,
+    combinator::{alt, delimited, eof, preceded, repeat, separated},
+    token::{any, none_of, one_of, take_till, take_while},
+    PResult, Parser,
+};
+
+// need to parse:
+// git commit -m "fix \"quoted\" bug" 'it'\''s' my\ file.txt
+pub fn parse_shell_words(s: &str) -> PResult<Vec<String>> {
+    let input = &mut (&*s);
+    let words = separated(0.., parse_word, (parse_blank, parse_blanks));
+    delimited(parse_blanks, words, (parse_blanks, eof)).parse_next(input)
+}
+
+fn parse_blanks(s: &mut &str) -> PResult<()> {
+    repeat(0.., parse_blank).parse_next(s)
+}
+
+fn parse_blank(s: &mut &str) -> PResult<()> {
+    alt((multispace1.void(), parse_line_continuation.void())).parse_next(s)
+}
+
+// outside quotes a backslash before a newline joins the lines
+fn parse_line_continuation<'a>(s: &mut &'a str) -> PResult<&'a str> {
+    alt(("\\\n", "\\\r\n")).parse_next(s)
+}
+
+// a word is a run of adjacent quoted and unquoted pieces, e.g. `--name="a b"`
+fn parse_word(s: &mut &str) -> PResult<String> {
+    repeat(
+        1..,
+        alt((
+            parse_single_quoted,
+            parse_double_quoted,
+            parse_line_continuation.value(String::new()),
+            preceded('\\', any).map(String::from),
+            take_while(1.., |c: char| !c.is_whitespace() && !"'\"\\".contains(c)).map(String::from),
+        )),
+    )
+    .fold(String::new, |mut acc, piece: String| {
+        acc.push_str(&piece);
+        acc
+    })
+    .parse_next(s)
+}
+
+// everything between single quotes is literal
+fn parse_single_quoted(s: &mut &str) -> PResult<String> {
+    delimited('\'', take_till(0.., '\''), '\'')
+        .map(String::from)
+        .parse_next(s)
+}
+
+// inside double quotes a backslash only escapes `"`, `\`, `$` and `` ` ``
+fn parse_double_quoted(s: &mut &str) -> PResult<String> {
+    let quoted_char = alt((
+        preceded('\\', one_of(['"', '\\', '$', '`'])),
+        '\\',
+        none_of(['"', '\\']),
+    ));
+    delimited('"', repeat(0.., quoted_char), '"').parse_next(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_shell_words_should_work() {
+        let ret = parse_shell_words("  ls -la   /tmp ").unwrap();
+        assert_eq!(ret, vec!["ls", "-la", "/tmp"]);
+        assert_eq!(parse_shell_words("").unwrap(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn parse_quoted_shell_words_should_work() {
+        let ret =
+            parse_shell_words(r#"git commit -m "fix \"quoted\" bug" 'it'\''s $HOME' --name="a b""#)
+                .unwrap();
+        assert_eq!(
+            ret,
+            vec![
+                "git",
+                "commit",
+                "-m",
+                r#"fix "quoted" bug"#,
+                "it's $HOME",
+                "--name=a b"
+            ]
+        );
+
+        let ret = parse_shell_words(r#"echo "C:\dir" '' """#).unwrap();
+        assert_eq!(ret, vec!["echo", r"C:\dir", "", ""]);
+    }
+
+    #[test]
+    fn parse_escaped_space_should_work() {
+        let ret = parse_shell_words(r"cat my\ file.txt other\\").unwrap();
+        assert_eq!(ret, vec!["cat", "my file.txt", r"other\"]);
+    }
+
+    #[test]
+    fn parse_line_continuation_should_work() {
+        let ret =
+            parse_shell_words("docker run \\\n  --rm \\\r\n  alp\\\nine 'a\\\nb'\\\n").unwrap();
+        assert_eq!(ret, vec!["docker", "run", "--rm", "alpine", "a\\\nb"]);
+        assert_eq!(parse_shell_words("\\\n").unwrap(), Vec::<String>::new());
+        assert_eq!(parse_shell_words("ls \\\n").unwrap(), vec!["ls"]);
+    }
+
+    #[test]
+    fn parse_unterminated_quote_should_fail() {
+        assert!(parse_shell_words(r#"echo "hello"#).is_err());
+        assert!(parse_shell_words("echo 'hello").is_err());
+    }
+}
@@ -0,0 +1,89 @@
+use winnow::{
+    ascii::line_ending,
+    combinator::{eof, opt, preceded, repeat, terminated},
+    error::{ErrMode, ErrorKind, ParserError},
+    stream::AsChar,
+    token::{take_till, take_while},
+    PResult, Parser,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmtpResponse {
+    pub code: u16,
+    pub lines: Vec<String>,
+}
+
+// need to parse:
+// 250-mail.example.com\r\n250-SIZE 35882577\r\n250 HELP\r\n
+pub fn parse_smtp_response(s: &str) -> PResult<SmtpResponse> {
+    let input = &mut (&*s);
+    // every line but the last has a `-` after the code
+    let continued: Vec<(u16, String)> =
+        repeat(0.., terminated(parse_continued_line, line_ending)).parse_next(input)?;
+    let (code, last) = terminated(parse_last_line, (opt(line_ending), eof)).parse_next(input)?;
+    if continued.iter().any(|(c, _)| *c != code) {
+        return Err(ErrMode::from_error_kind(input, ErrorKind::Verify));
+    }
+    let mut lines: Vec<String> = continued.into_iter().map(|(_, line)| line).collect();
+    lines.push(last);
+    Ok(SmtpResponse { code, lines })
+}
+
+fn parse_continued_line(s: &mut &str) -> PResult<(u16, String)> {
+    let text = preceded('-', take_till(0.., ['\r', '\n']));
+    (parse_code, text.map(str::to_string)).parse_next(s)
+}
+
+// the text is optional on the final line, e.g. a bare `354`
+fn parse_last_line(s: &mut &str) -> PResult<(u16, String)> {
+    let text = opt(preceded(' ', take_till(0.., ['\r', '\n'])));
+    (
+        parse_code,
+        text.map(|t: Option<&str>| t.unwrap_or_default().to_string()),
+    )
+        .parse_next(s)
+}
+
+fn parse_code(s: &mut &str) -> PResult<u16> {
+    take_while(3, AsChar::is_dec_digit).parse_to().parse_next(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_single_line_response_should_work() {
+        let ret = parse_smtp_response("250 OK\r\n").unwrap();
+        assert_eq!(
+            ret,
+            SmtpResponse {
+                code: 250,
+                lines: vec!["OK".to_string()],
+            }
+        );
+        let ret = parse_smtp_response("354").unwrap();
+        assert_eq!(ret.code, 354);
+        assert_eq!(ret.lines, vec![""]);
+    }
+
+    #[test]
+    fn parse_multi_line_response_should_work() {
+        let ret = parse_smtp_response("250-mail.example.com\r\n250-SIZE 35882577\r\n250 HELP\r\n")
+            .unwrap();
+        assert_eq!(
+            ret,
+            SmtpResponse {
+                code: 250,
+                lines: vec![
+                    "mail.example.com".to_string(),
+                    "SIZE 35882577".to_string(),
+                    "HELP".to_string(),
+                ],
+            }
+        );
+        assert!(parse_smtp_response("250-first\r\n").is_err());
+        assert!(parse_smtp_response("250-first\r\n550 last\r\n").is_err());
+        assert!(parse_smtp_response("250 first\r\n250 second\r\n").is_err());
+    }
+}
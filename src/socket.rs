@@ -0,0 +1,76 @@
+use std::net::IpAddr;
+
+use winnow::{
+    ascii::{alphanumeric1, digit1, space0, space1},
+    combinator::{eof, separated_pair, terminated},
+    token::take_till,
+    PResult, Parser,
+};
+
+use crate::nginx_log::parse_ip;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SocketEntry {
+    pub proto: String,
+    pub state: String,
+    pub recvq: u64,
+    pub sendq: u64,
+    pub local: (IpAddr, u16),
+    pub peer: (IpAddr, u16),
+}
+
+// need to parse:
+// tcp   ESTAB  0  0  10.0.0.1:443  10.0.0.2:51000
+pub fn parse_socket_line(s: &str) -> PResult<SocketEntry> {
+    let input = &mut s.trim();
+    let proto = terminated(alphanumeric1, space1).parse_next(input)?;
+    let state = terminated(take_till(1.., char::is_whitespace), space1).parse_next(input)?;
+    let recvq = terminated(digit1.parse_to(), space1).parse_next(input)?;
+    let sendq = terminated(digit1.parse_to(), space1).parse_next(input)?;
+    let local = terminated(parse_endpoint, space1).parse_next(input)?;
+    let peer = terminated(parse_endpoint, (space0, eof)).parse_next(input)?;
+    Ok(SocketEntry {
+        proto: proto.to_string(),
+        state: state.to_string(),
+        recvq,
+        sendq,
+        local,
+        peer,
+    })
+}
+
+// only IPv4 for now since `parse_ip` doesn't handle bracketed IPv6 addresses yet
+fn parse_endpoint(s: &mut &str) -> PResult<(IpAddr, u16)> {
+    separated_pair(parse_ip, ':', digit1.parse_to()).parse_next(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    #[test]
+    fn parse_socket_line_should_work() {
+        let ret = parse_socket_line("tcp   ESTAB  0      36     10.0.0.1:443     10.0.0.2:51000")
+            .unwrap();
+        assert_eq!(
+            ret,
+            SocketEntry {
+                proto: "tcp".to_string(),
+                state: "ESTAB".to_string(),
+                recvq: 0,
+                sendq: 36,
+                local: (IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)), 443),
+                peer: (IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2)), 51000),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_socket_line_with_bad_input_should_fail() {
+        assert!(parse_socket_line("tcp ESTAB 0 0 10.0.0.1:443").is_err());
+        assert!(parse_socket_line("tcp ESTAB 0 0 10.0.0.1:443 10.0.0.2:70000").is_err());
+        assert!(parse_socket_line("tcp LISTEN 0 128 0.0.0.0:22 0.0.0.0:*").is_err());
+    }
+}
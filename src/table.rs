@@ -0,0 +1,60 @@
+use winnow::{
+    ascii::{line_ending, space0, space1},
+    combinator::{delimited, eof, preceded, repeat, separated, terminated},
+    error::{ErrMode, ErrorKind, ParserError},
+    token::take_till,
+    PResult, Parser,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+// need to parse:
+//  r  b   swpd   free
+//  1  0      0 812344
+pub fn parse_table(input: &str) -> PResult<Table> {
+    let input = &mut input.trim_matches(['\r', '\n']);
+    let headers = parse_row(input)?;
+    let rows: Vec<Vec<String>> = terminated(
+        repeat(
+            0..,
+            preceded(repeat::<_, _, (), _, _>(1.., line_ending), parse_row),
+        ),
+        (repeat::<_, _, (), _, _>(0.., line_ending), eof),
+    )
+    .parse_next(input)?;
+    if rows.iter().any(|row| row.len() != headers.len()) {
+        return Err(ErrMode::from_error_kind(input, ErrorKind::Verify));
+    }
+    Ok(Table { headers, rows })
+}
+
+fn parse_row(input: &mut &str) -> PResult<Vec<String>> {
+    let cell = take_till(1.., [' ', '\t', '\r', '\n']).map(|s: &str| s.to_string());
+    delimited(space0, separated(1.., cell, space1), space0).parse_next(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_table_should_work() {
+        let input = "  r   b   swpd\n  1   0      0\n 12  3   1024\n";
+        let table = parse_table(input).unwrap();
+        assert_eq!(table.headers, vec!["r", "b", "swpd"]);
+        assert_eq!(
+            table.rows,
+            vec![vec!["1", "0", "0"], vec!["12", "3", "1024"]]
+        );
+    }
+
+    #[test]
+    fn parse_table_with_mismatched_row_should_fail() {
+        assert!(parse_table("a b c\n1 2 3\n4 5").is_err());
+        assert!(parse_table("a b c\n1 2 3 4").is_err());
+    }
+}
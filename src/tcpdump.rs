@@ -0,0 +1,83 @@
+use std::net::IpAddr;
+
+use winnow::{
+    ascii::{alphanumeric1, digit1, space1},
+    combinator::{delimited, preceded, separated_pair, terminated},
+    token::take_till,
+    PResult, Parser,
+};
+
+use crate::nginx_log::parse_ip;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PacketSummary {
+    pub time: String,
+    pub proto: String,
+    pub src: (IpAddr, u16),
+    pub dst: (IpAddr, u16),
+    pub flags: String,
+}
+
+// need to parse:
+// 12:34:56.789 IP 10.0.0.1.443 > 10.0.0.2.51000: Flags [S], seq 1, win 64240, length 0
+pub fn parse_tcpdump_line(s: &str) -> PResult<PacketSummary> {
+    let input = &mut (&*s);
+    let time = terminated(take_till(1.., ' '), space1).parse_next(input)?;
+    let proto = terminated(alphanumeric1, space1).parse_next(input)?;
+    let (src, dst) =
+        terminated(separated_pair(parse_endpoint, " > ", parse_endpoint), ':').parse_next(input)?;
+    // whatever follows the flags (seq, ack, win, length...) is ignored
+    let flags = preceded(
+        (space1, "Flags", space1),
+        delimited('[', take_till(0.., ']'), ']'),
+    )
+    .parse_next(input)?;
+    Ok(PacketSummary {
+        time: time.to_string(),
+        proto: proto.to_string(),
+        src,
+        dst,
+        flags: flags.to_string(),
+    })
+}
+
+// the port is appended to the address with a dot, e.g. `10.0.0.1.443`
+fn parse_endpoint(s: &mut &str) -> PResult<(IpAddr, u16)> {
+    separated_pair(parse_ip, '.', digit1.parse_to()).parse_next(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    #[test]
+    fn parse_tcpdump_line_should_work() {
+        let ret = parse_tcpdump_line(
+            "12:34:56.789 IP 10.0.0.1.443 > 10.0.0.2.51000: Flags [S], seq 1, win 64240, length 0",
+        )
+        .unwrap();
+        assert_eq!(
+            ret,
+            PacketSummary {
+                time: "12:34:56.789".to_string(),
+                proto: "IP".to_string(),
+                src: (IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)), 443),
+                dst: (IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2)), 51000),
+                flags: "S".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_tcpdump_non_tcp_line_should_fail() {
+        assert!(parse_tcpdump_line(
+            "12:34:56.789 IP 10.0.0.1.53 > 10.0.0.2.51000: 123+ A? example.com. (29)"
+        )
+        .is_err());
+        assert!(
+            parse_tcpdump_line("12:34:56.789 ARP, Request who-has 10.0.0.2 tell 10.0.0.1").is_err()
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use winnow::{
+    ascii::line_ending,
+    combinator::{alt, dispatch, empty, eof, fail, preceded, repeat, separated, terminated},
+    token::{any, none_of},
+    PResult, Parser,
+};
+
+// need to parse:
+// name\tcomment\nJohn\tsays\\thi\n
+pub fn parse_tsv(input: &str) -> PResult<Vec<Vec<String>>> {
+    // trailing newlines don't start another row
+    let input = &mut input.trim_end_matches(['\r', '\n']);
+    if input.is_empty() {
+        return Ok(vec![]);
+    }
+    terminated(separated(1.., parse_row, line_ending), eof).parse_next(input)
+}
+
+fn parse_row(s: &mut &str) -> PResult<Vec<String>> {
+    separated(1.., parse_field, '\t').parse_next(s)
+}
+
+// fields aren't quoted, tabs, newlines and backslashes inside them are escaped instead
+fn parse_field(s: &mut &str) -> PResult<String> {
+    let escaped = dispatch! {any;
+        't' => empty.value('\t'),
+        'n' => empty.value('\n'),
+        'r' => empty.value('\r'),
+        '\\' => empty.value('\\'),
+        _ => fail,
+    };
+    repeat(
+        0..,
+        alt((preceded('\\', escaped), none_of(['\t', '\r', '\n', '\\']))),
+    )
+    .parse_next(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_tsv_should_work() {
+        let ret = parse_tsv("name\tage\nJohn\t43").unwrap();
+        assert_eq!(ret, vec![vec!["name", "age"], vec!["John", "43"]]);
+
+        let ret = parse_tsv("a\t\tc\r\n\tb").unwrap();
+        assert_eq!(ret, vec![vec!["a", "", "c"], vec!["", "b"]]);
+    }
+
+    #[test]
+    fn parse_tsv_with_escapes_should_work() {
+        let ret = parse_tsv("John\tsays\\thi\\nthere\\\\").unwrap();
+        assert_eq!(ret, vec![vec!["John", "says\thi\nthere\\"]]);
+        assert!(parse_tsv("bad\\x").is_err());
+    }
+
+    #[test]
+    fn parse_tsv_with_trailing_newline_should_work() {
+        let ret = parse_tsv("a\tb\n").unwrap();
+        assert_eq!(ret, vec![vec!["a", "b"]]);
+        let ret = parse_tsv("a\tb\n\n").unwrap();
+        assert_eq!(ret, vec![vec!["a", "b"]]);
+        assert_eq!(parse_tsv("\n").unwrap(), Vec::<Vec<String>>::new());
+    }
+}
@@ -0,0 +1,130 @@
+use winnow::{
+    ascii::multispace0,
+    combinator::{alt, delimited, opt, preceded, repeat},
+    token::take_till,
+    PResult, Parser,
+};
+
+/// Browser and OS details extracted from a `User-Agent` header.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct UserAgentInfo {
+    pub browser: Option<String>,
+    pub browser_version: Option<String>,
+    pub os: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Part<'a> {
+    Product(&'a str, Option<&'a str>),
+    Comment(&'a str),
+}
+
+// need to parse:
+// Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36
+pub fn parse_user_agent(s: &str) -> UserAgentInfo {
+    let Ok(parts) = parse_parts(s) else {
+        return UserAgentInfo::default();
+    };
+    // only browsers identify as Mozilla, anything else (curl, APT, bots) is unknown
+    if !matches!(parts.first(), Some(Part::Product("Mozilla", _))) {
+        return UserAgentInfo::default();
+    }
+    let version_of = |name: &str| {
+        parts.iter().find_map(|p| match p {
+            Part::Product(n, v) if *n == name => Some(v.unwrap_or_default().to_string()),
+            _ => None,
+        })
+    };
+    // order matters, e.g. Edge also claims to be Chrome and Chrome claims to be Safari
+    let (browser, browser_version) = if let Some(v) = version_of("Edg") {
+        (Some("Edge"), Some(v))
+    } else if let Some(v) = version_of("Firefox") {
+        (Some("Firefox"), Some(v))
+    } else if let Some(v) = version_of("Chrome") {
+        (Some("Chrome"), Some(v))
+    } else if version_of("Safari").is_some() {
+        (Some("Safari"), version_of("Version"))
+    } else {
+        (None, None)
+    };
+    let os = parts.iter().find_map(|p| match p {
+        Part::Comment(c) => detect_os(c),
+        _ => None,
+    });
+    UserAgentInfo {
+        browser: browser.map(String::from),
+        browser_version,
+        os: os.map(String::from),
+    }
+}
+
+fn parse_parts(s: &str) -> PResult<Vec<Part<'_>>> {
+    let input = &mut (&*s);
+    let product = (
+        take_till(1.., |c: char| c == '/' || c == '(' || c.is_whitespace()),
+        opt(preceded('/', take_till(1.., |c: char| c.is_whitespace()))),
+    )
+        .map(|(name, version)| Part::Product(name, version));
+    let comment = delimited('(', take_till(0.., ')'), ')').map(Part::Comment);
+    repeat(
+        1..,
+        delimited(multispace0, alt((comment, product)), multispace0),
+    )
+    .parse_next(input)
+}
+
+fn detect_os(comment: &str) -> Option<&'static str> {
+    // Android and iOS comments also mention Linux and Mac OS X, check them first
+    [
+        ("Android", "Android"),
+        ("iPhone", "iOS"),
+        ("iPad", "iOS"),
+        ("Windows", "Windows"),
+        ("Mac OS X", "macOS"),
+        ("Linux", "Linux"),
+    ]
+    .into_iter()
+    .find(|(needle, _)| comment.contains(needle))
+    .map(|(_, os)| os)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_chrome_on_windows_should_work() {
+        let ret = parse_user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36");
+        assert_eq!(
+            ret,
+            UserAgentInfo {
+                browser: Some("Chrome".to_string()),
+                browser_version: Some("120.0.0.0".to_string()),
+                os: Some("Windows".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_other_browsers_should_work() {
+        let ret = parse_user_agent(
+            "Mozilla/5.0 (X11; Ubuntu; Linux x86_64; rv:121.0) Gecko/20100101 Firefox/121.0",
+        );
+        assert_eq!(ret.browser.as_deref(), Some("Firefox"));
+        assert_eq!(ret.browser_version.as_deref(), Some("121.0"));
+        assert_eq!(ret.os.as_deref(), Some("Linux"));
+
+        let ret = parse_user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 14_2) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.2 Safari/605.1.15");
+        assert_eq!(ret.browser.as_deref(), Some("Safari"));
+        assert_eq!(ret.browser_version.as_deref(), Some("17.2"));
+        assert_eq!(ret.os.as_deref(), Some("macOS"));
+    }
+
+    #[test]
+    fn parse_unknown_agent_should_return_none() {
+        let ret = parse_user_agent("Debian APT-HTTP/1.3 (0.8.16~exp12ubuntu10.21)");
+        assert_eq!(ret, UserAgentInfo::default());
+
+        assert_eq!(parse_user_agent(""), UserAgentInfo::default());
+    }
+}
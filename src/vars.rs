@@ -0,0 +1,124 @@
+use anyhow::{anyhow, bail, Result};
+use winnow::{
+    combinator::{alt, cut_err, eof, opt, preceded, repeat, terminated},
+    token::{any, take_till, take_while},
+    PResult, Parser,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Piece<'a> {
+    Literal(&'a str),
+    Var {
+        name: &'a str,
+        default: Option<&'a str>,
+    },
+}
+
+/// Replace `${VAR}`, `$VAR` and `${VAR:-default}` with values from `lookup`, `\$` is a
+/// literal dollar. The default applies when the variable is unset or empty, an unclosed
+/// `${` is an error.
+pub fn expand_vars(input: &str, lookup: impl Fn(&str) -> Option<String>) -> Result<String> {
+    let pieces = parse_pieces(input).map_err(|e| anyhow!("Failed to parse template: {:?}", e))?;
+    let mut ret = String::with_capacity(input.len());
+    for piece in pieces {
+        match piece {
+            Piece::Literal(s) => ret.push_str(s),
+            Piece::Var { name, default } => match (lookup(name), default) {
+                (Some(v), Some(default)) if v.is_empty() => ret.push_str(default),
+                (Some(v), _) => ret.push_str(&v),
+                (None, Some(default)) => ret.push_str(default),
+                (None, None) => bail!("Undefined variable: {}", name),
+            },
+        }
+    }
+    Ok(ret)
+}
+
+fn parse_pieces(s: &str) -> PResult<Vec<Piece<'_>>> {
+    let input = &mut (&*s);
+    let piece = alt((
+        "\\$".value(Piece::Literal("$")),
+        preceded('$', parse_braced_var),
+        preceded('$', parse_name).map(|name| Piece::Var {
+            name,
+            default: None,
+        }),
+        take_till(1.., ['$', '\\']).map(Piece::Literal),
+        // a lone `$` or `\` that doesn't start anything is kept as is
+        any.take().map(Piece::Literal),
+    ));
+    terminated(repeat(0.., piece), eof).parse_next(input)
+}
+
+// once `${` is seen the variable must be well formed and closed
+fn parse_braced_var<'a>(s: &mut &'a str) -> PResult<Piece<'a>> {
+    let default = preceded(":-", take_till(0.., '}'));
+    preceded('{', cut_err(terminated((parse_name, opt(default)), '}')))
+        .map(|(name, default)| Piece::Var { name, default })
+        .parse_next(s)
+}
+
+fn parse_name<'a>(s: &mut &'a str) -> PResult<&'a str> {
+    (
+        take_while(1, |c: char| c.is_ascii_alphabetic() || c == '_'),
+        take_while(0.., |c: char| c.is_ascii_alphanumeric() || c == '_'),
+    )
+        .take()
+        .parse_next(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "HOME" => Some("/home/john".to_string()),
+            "USER" => Some("john".to_string()),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn expand_vars_should_work() -> Result<()> {
+        assert_eq!(
+            expand_vars("${HOME}/.config for $USER!", lookup)?,
+            "/home/john/.config for john!"
+        );
+        assert_eq!(expand_vars("no vars here", lookup)?, "no vars here");
+
+        let err = expand_vars("hello $NAME", lookup).unwrap_err();
+        assert_eq!(err.to_string(), "Undefined variable: NAME");
+
+        Ok(())
+    }
+
+    #[test]
+    fn expand_vars_with_default_should_work() -> Result<()> {
+        assert_eq!(
+            expand_vars("port=${PORT:-8080} user=${USER:-nobody}", lookup)?,
+            "port=8080 user=john"
+        );
+        assert_eq!(expand_vars("${EMPTY:-fallback}", lookup)?, "fallback");
+
+        Ok(())
+    }
+
+    #[test]
+    fn expand_vars_with_escaped_dollar_should_work() -> Result<()> {
+        assert_eq!(
+            expand_vars(r"costs \$5, not $ 5 or C:\dir", lookup)?,
+            r"costs $5, not $ 5 or C:\dir"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn expand_vars_with_unclosed_brace_should_fail() {
+        for s in ["${HOME", "path ${HOME/bin", "${USER:-nobody", "${}"] {
+            assert!(expand_vars(s, lookup).is_err(), "{s:?} should fail");
+        }
+    }
+}
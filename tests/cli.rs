@@ -0,0 +1,63 @@
+use std::process::{Command, Output};
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_grammar"))
+        .args(args)
+        .output()
+        .expect("failed to run grammar")
+}
+
+#[test]
+fn json_subcommand_should_work() {
+    let output = run(&["json", "tests/fixtures/sample.json"]);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(r#""city": "New York""#));
+    assert!(stdout.contains(r#""zip": 10001"#));
+}
+
+#[test]
+fn json_subcommand_should_report_error_position() {
+    let output = run(&["json", "tests/fixtures/invalid.json"]);
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("line 3, column 12"));
+}
+
+#[test]
+fn nginx_subcommand_should_work() {
+    let output = run(&["nginx", "tests/fixtures/access.log"]);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 2);
+    assert!(stdout.contains("addr: 80.91.33.133"));
+}
+
+#[test]
+fn nginx_subcommand_should_skip_comments_and_blank_lines() {
+    let output = run(&["nginx", "tests/fixtures/commented.log"]);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 2);
+    assert!(stdout.contains("addr: 80.91.33.133"));
+}
+
+#[test]
+fn nginx_subcommand_should_report_error_position() {
+    let output = run(&["nginx", "tests/fixtures/invalid.log"]);
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("line 2, column 48"));
+}
+
+#[test]
+fn unknown_subcommand_should_fail() {
+    let output = run(&["yaml", "tests/fixtures/sample.json"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("usage"));
+}